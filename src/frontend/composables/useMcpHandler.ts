@@ -42,19 +42,25 @@ export function useMcpHandler() {
    * 显示MCP弹窗
    */
   async function showMcpDialog(request: any) {
-    // 获取Telegram配置，检查是否需要隐藏前端弹窗
+    // 寸止进程已按免打扰时间段计算好路由时直接使用
+    const route = request?.route
     let shouldShowFrontendPopup = true
-    try {
-      const telegramConfig = await invoke('get_telegram_config')
-      // 如果Telegram启用且配置了隐藏前端弹窗，则不显示前端弹窗
-      if (telegramConfig && (telegramConfig as any).enabled && (telegramConfig as any).hide_frontend_popup) {
-        shouldShowFrontendPopup = false
-        console.log('🔕 根据Telegram配置，隐藏前端弹窗')
-      }
+    if (route) {
+      shouldShowFrontendPopup = route.backend !== 'telegram'
     }
-    catch (error) {
-      console.error('获取Telegram配置失败:', error)
-      // 配置获取失败时，保持默认行为（显示弹窗）
+    else {
+      try {
+        const telegramConfig = await invoke('get_telegram_config')
+        // 如果Telegram启用且配置了隐藏前端弹窗，则不显示前端弹窗
+        if (telegramConfig && (telegramConfig as any).enabled && (telegramConfig as any).hide_frontend_popup) {
+          shouldShowFrontendPopup = false
+          console.log('🔕 根据Telegram配置，隐藏前端弹窗')
+        }
+      }
+      catch (error) {
+        console.error('获取Telegram配置失败:', error)
+        // 配置获取失败时，保持默认行为（显示弹窗）
+      }
     }
 
     // 根据配置决定是否显示前端弹窗
@@ -67,21 +73,24 @@ export function useMcpHandler() {
       console.log('🔕 跳过前端弹窗显示，仅使用Telegram交互')
     }
 
-    // 播放音频通知（无论是否显示弹窗都播放）
-    try {
-      await invoke('play_notification_sound')
-    }
-    catch (error) {
-      console.error('播放音频通知失败:', error)
+    // 播放音频通知（无论是否显示弹窗都播放，免打扰时间段可静音）
+    if (!route?.mute_audio) {
+      try {
+        await invoke('play_notification_sound')
+      }
+      catch (error) {
+        console.error('播放音频通知失败:', error)
+      }
     }
 
-    // 启动Telegram同步（无论是否显示弹窗都启动）
+    // 启动Telegram同步（无论是否显示弹窗都启动，路由为仅弹窗时跳过）
     try {
-      if (request?.message) {
+      if (request?.message && route?.backend !== 'popup') {
         await invoke('start_telegram_sync', {
           message: request.message,
           predefinedOptions: request.predefined_options || [],
           isMarkdown: request.is_markdown || false,
+          projectPath: request.project_path || null,
         })
         console.log('✅ Telegram同步启动成功')
       }
@@ -122,6 +131,12 @@ export function useMcpHandler() {
       await listen('mcp-request', (event) => {
         showMcpDialog(event.payload)
       })
+
+      // 常驻模式下回答完成后窗口被隐藏，重置弹窗状态以便下次打开设置界面
+      await listen('mcp-request-completed', () => {
+        mcpRequest.value = null
+        showMcpPopup.value = false
+      })
     }
     catch (error) {
       console.error('设置MCP事件监听器失败:', error)
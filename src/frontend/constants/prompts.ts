@@ -40,6 +40,16 @@ export const PROMPT_SECTIONS = {
 - 对话开始时查询 \`回忆\` 参数 \`project_path\` 为 git 的根目录
 - 当发现用户输入"请记住："时，要对用户的消息进行总结后调用 \`记忆\` 的 add 功能添加记忆
 - 使用 \`记忆\` 的 add 功能添加新记忆（content + category: rule/preference/pattern/context）
+- 记忆较多时，使用 \`search\` 或带 \`query\` 的 \`回忆\` 只获取与当前任务相关的记忆
+- 适用于所有项目的个人偏好使用 \`scope: global\` 保存，团队约定使用 \`scope: team\`
+- 可通过 \`tags\` 为记忆打标签，并在 \`list\`/\`search\`/\`回忆\` 时按标签筛选
+- 规则发生变化时，先用 \`list\` 查到记忆 ID，再用 \`update\`/\`delete\`/\`move\` 修改原记忆，不要追加相互矛盾的条目
+- 记忆出现重复或杂乱时使用 \`organize\` 整理：先预览差异，确认后带上返回的 \`plan_id\` 再次调用
+- 项目已有 CLAUDE.md、AGENTS.md、.cursorrules 等规则文件时，可用 \`import\` 导入为记忆，用 \`export\` 将记忆同步回这些文件
+- 只在当前分支成立的信息（如分支上的临时改动）添加时设置 \`branch: current\`，分支合并后用 \`promote\` 提升为通用记忆
+- 临时性的上下文（如正在排查的问题）添加时设置 \`ttl\`（如 \`7d\`），过期后不再被回忆
+- 误改或误删记忆时，用 \`history\` 查到变更ID，再用 \`undo\` 撤销
+- 不要把 API 密钥、密码、连接串等敏感信息写入记忆，只记录它们的存放位置
 - 仅在重要变更时更新记忆，保持简洁`,
   } as PromptSection,
 
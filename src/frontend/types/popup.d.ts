@@ -5,6 +5,14 @@ export interface McpRequest {
   message: string
   predefined_options?: string[]
   is_markdown?: boolean
+  project_path?: string
+  route?: RouteDecision
+}
+
+// 交互路由决策（由寸止进程按免打扰时间段计算）
+export interface RouteDecision {
+  backend: 'popup' | 'telegram' | 'both'
+  mute_audio: boolean
 }
 
 // 自定义prompt类型定义
@@ -22,6 +30,7 @@ export interface CustomPrompt {
   template_true?: string // 开关为true时的模板
   template_false?: string // 开关为false时的模板
   current_state?: boolean // 当前开关状态
+  append_to_response?: boolean // 普通模板是否追加到每次回复
 }
 
 // 自定义prompt配置
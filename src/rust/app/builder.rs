@@ -42,6 +42,23 @@ pub fn build_tauri_app() -> Builder<tauri::Wry> {
             set_window_config,
             get_reply_config,
             set_reply_config,
+            get_locale,
+            set_locale,
+            get_policy_config,
+            set_policy_config,
+            get_routing_config,
+            set_routing_config,
+            get_enhance_config,
+            set_enhance_config,
+            render_enhance_prompt,
+            llm_enhance_text,
+            get_memory_config,
+            set_memory_config,
+            migrate_project_memory,
+            convert_memory_layout,
+            organize_memory,
+            import_agent_rules,
+            export_agent_rules,
             get_window_settings,
             set_window_settings,
             get_window_settings_for_mode,
@@ -61,6 +78,19 @@ pub fn build_tauri_app() -> Builder<tauri::Wry> {
             get_font_size_options,
             reset_font_config,
 
+            // 记忆命令
+            list_memory_projects,
+            get_memory_categories,
+            list_memories,
+            search_memories,
+            update_memory_entry,
+            move_memory_entry,
+            promote_memory_entry,
+            delete_memory_entry,
+            list_memory_history,
+            undo_memory_change,
+            open_memory_folder,
+
             // MCP 命令
             get_mcp_tools_config,
             set_mcp_tool_enabled,
@@ -96,6 +126,8 @@ pub fn build_tauri_app() -> Builder<tauri::Wry> {
             get_shortcut_config,
             update_shortcut_binding,
             reset_shortcuts_to_default,
+            export_prompt_pack,
+            import_prompt_pack,
 
             // 配置管理命令
             get_config_file_path,
@@ -1,4 +1,6 @@
 use crate::config::load_standalone_telegram_config;
+use crate::mcp::handlers::BACKEND_TELEGRAM;
+use crate::mcp::types::PopupRequest;
 use crate::telegram::handle_telegram_only_mcp_request;
 use crate::log_important;
 use crate::app::builder::run_tauri_app;
@@ -18,6 +20,7 @@ pub fn handle_cli_args() -> Result<()> {
             match args[1].as_str() {
                 "--help" | "-h" => print_help(),
                 "--version" | "-v" => print_version(),
+                "--daemon" => run_tauri_app(),
                 _ => {
                     eprintln!("未知参数: {}", args[1]);
                     print_help();
@@ -42,30 +45,43 @@ pub fn handle_cli_args() -> Result<()> {
 
 /// 处理MCP请求
 fn handle_mcp_request(request_file: &str) -> Result<()> {
-    // 检查Telegram配置，决定是否启用纯Telegram模式
-    match load_standalone_telegram_config() {
-        Ok(telegram_config) => {
-            if telegram_config.enabled && telegram_config.hide_frontend_popup {
-                // 纯Telegram模式：不启动GUI，直接处理
-                if let Err(e) = tokio::runtime::Runtime::new()
-                    .unwrap()
-                    .block_on(handle_telegram_only_mcp_request(request_file))
-                {
-                    log_important!(error, "处理Telegram请求失败: {}", e);
-                    std::process::exit(1);
-                }
-            } else {
-                // 正常模式：启动GUI处理弹窗
-                run_tauri_app();
-            }
+    if is_telegram_only_request(request_file) {
+        // 纯Telegram模式：不启动GUI，直接处理
+        if let Err(e) = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(handle_telegram_only_mcp_request(request_file))
+        {
+            log_important!(error, "处理Telegram请求失败: {}", e);
+            std::process::exit(1);
         }
+    } else {
+        // 正常模式：启动GUI处理弹窗
+        run_tauri_app();
+    }
+    Ok(())
+}
+
+/// 判断请求是否只通过Telegram交互
+///
+/// 优先使用寸止进程计算好的路由，旧版本请求没有路由时按Telegram配置判断
+fn is_telegram_only_request(request_file: &str) -> bool {
+    let route = std::fs::read_to_string(request_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<PopupRequest>(&content).ok())
+        .and_then(|request| request.route);
+
+    if let Some(route) = route {
+        return route.backend == BACKEND_TELEGRAM;
+    }
+
+    match load_standalone_telegram_config() {
+        Ok(telegram_config) => telegram_config.enabled && telegram_config.hide_frontend_popup,
         Err(e) => {
             log_important!(warn, "加载Telegram配置失败: {}，使用默认GUI模式", e);
             // 配置加载失败时，使用默认行为（启动GUI）
-            run_tauri_app();
+            false
         }
     }
-    Ok(())
 }
 
 /// 显示帮助信息
@@ -75,6 +91,7 @@ fn print_help() {
     println!("用法:");
     println!("  等一下                    启动设置界面");
     println!("  等一下 --mcp-request <文件>  处理 MCP 请求");
+    println!("  等一下 --daemon           常驻托盘，接收寸止的弹窗请求");
     println!("  等一下 --help             显示此帮助信息");
     println!("  等一下 --version          显示版本信息");
 }
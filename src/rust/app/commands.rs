@@ -14,6 +14,7 @@ pub use crate::ui::{
     audio::*,
     audio_assets::*,
     font_commands::*,
+    memory_commands::*,
     updater::*,
     exit::*,
     exit_handler::*,
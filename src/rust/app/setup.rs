@@ -1,6 +1,7 @@
 use crate::config::{AppState, load_config_and_apply_window_settings};
 use crate::ui::{initialize_audio_asset_manager, setup_window_event_listeners};
 use crate::ui::exit_handler::setup_exit_handlers;
+use crate::daemon::{is_daemon_mode, start_daemon_server, setup_daemon_tray};
 use crate::log_important;
 use tauri::{AppHandle, Manager};
 
@@ -26,5 +27,21 @@ pub async fn setup_application(app_handle: &AppHandle) -> Result<(), String> {
         log_important!(warn, "设置退出处理器失败: {}", e);
     }
 
+    // 常驻模式：启动托盘和本地监听，窗口默认隐藏，收到请求时再显示
+    if is_daemon_mode() {
+        if let Err(e) = setup_daemon_tray(app_handle) {
+            log_important!(warn, "设置托盘图标失败: {}", e);
+        }
+
+        if let Err(e) = start_daemon_server(app_handle) {
+            log_important!(error, "启动daemon监听失败: {}", e);
+            return Err(format!("启动daemon监听失败: {}", e));
+        }
+
+        if let Some(window) = app_handle.get_webview_window("main") {
+            let _ = window.hide();
+        }
+    }
+
     Ok(())
 }
@@ -1,5 +1,7 @@
+pub mod pack;
 pub mod settings;
 pub mod storage;
 
+pub use pack::*;
 pub use settings::*;
 pub use storage::*;
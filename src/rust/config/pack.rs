@@ -0,0 +1,220 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::settings::{CustomPrompt, CustomPromptConfig, ShortcutBinding, ShortcutConfig};
+
+/// 当前模板包格式版本
+pub const PACK_VERSION: u32 = 1;
+
+/// 可分享的快捷模板和快捷键包
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PromptPack {
+    pub version: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub exported_at: Option<String>,
+    #[serde(default)]
+    pub prompts: Vec<CustomPrompt>,
+    #[serde(default)]
+    pub shortcuts: BTreeMap<String, ShortcutBinding>,
+}
+
+/// 模板包文件格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PackFormat {
+    Json,
+    Toml,
+}
+
+impl PackFormat {
+    /// 解析格式名称或文件扩展名
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().trim_start_matches('.').to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "toml" => Ok(Self::Toml),
+            other => anyhow::bail!("不支持的模板包格式: {}", other),
+        }
+    }
+}
+
+/// ID冲突时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportStrategy {
+    /// 保留本地条目，跳过冲突项
+    Merge,
+    /// 用模板包中的条目覆盖本地条目
+    Overwrite,
+}
+
+impl ImportStrategy {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim() {
+            "merge" => Ok(Self::Merge),
+            "overwrite" => Ok(Self::Overwrite),
+            other => anyhow::bail!("未知的导入方式: {}，应为 merge 或 overwrite", other),
+        }
+    }
+}
+
+/// 导入结果报告
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ImportReport {
+    pub added_prompts: Vec<String>,
+    pub updated_prompts: Vec<String>,
+    pub skipped_prompts: Vec<String>,
+    pub added_shortcuts: Vec<String>,
+    pub updated_shortcuts: Vec<String>,
+    pub skipped_shortcuts: Vec<String>,
+    pub conflicts: Vec<String>, // 与本地ID冲突的条目
+    pub limit_reached: bool,    // 是否因 max_prompts 限制跳过了新条目
+}
+
+/// 导出选中的快捷模板和快捷键，ID列表为空时导出全部
+pub fn export_pack(
+    prompt_config: &CustomPromptConfig,
+    shortcut_config: &ShortcutConfig,
+    prompt_ids: &[String],
+    shortcut_ids: &[String],
+    name: Option<String>,
+    format: PackFormat,
+) -> Result<String> {
+    let mut prompts: Vec<CustomPrompt> = prompt_config
+        .prompts
+        .iter()
+        .filter(|p| prompt_ids.is_empty() || prompt_ids.contains(&p.id))
+        .cloned()
+        .collect();
+    prompts.sort_by_key(|p| p.sort_order);
+
+    let shortcuts = shortcut_config
+        .shortcuts
+        .iter()
+        .filter(|(id, _)| shortcut_ids.is_empty() || shortcut_ids.contains(id))
+        .map(|(id, binding)| (id.clone(), binding.clone()))
+        .collect();
+
+    let pack = PromptPack {
+        version: PACK_VERSION,
+        name,
+        exported_at: Some(chrono::Utc::now().to_rfc3339()),
+        prompts,
+        shortcuts,
+    };
+
+    Ok(match format {
+        PackFormat::Json => serde_json::to_string_pretty(&pack)?,
+        PackFormat::Toml => toml::to_string_pretty(&pack)?,
+    })
+}
+
+/// 解析模板包内容，自动识别JSON和TOML
+pub fn parse_pack(content: &str) -> Result<PromptPack> {
+    let pack: PromptPack = if content.trim_start().starts_with('{') {
+        serde_json::from_str(content).map_err(|e| anyhow::anyhow!("模板包JSON格式无效: {}", e))?
+    } else {
+        toml::from_str(content).map_err(|e| anyhow::anyhow!("模板包TOML格式无效: {}", e))?
+    };
+
+    if pack.version == 0 || pack.version > PACK_VERSION {
+        anyhow::bail!("不支持的模板包版本: {}（当前支持 {}）", pack.version, PACK_VERSION);
+    }
+
+    Ok(pack)
+}
+
+/// 把模板包应用到配置
+///
+/// 冲突项按 `strategy` 处理；新增快捷模板受 `max_prompts` 限制，超出的条目会被跳过并在报告中标记
+pub fn apply_pack(
+    prompt_config: &mut CustomPromptConfig,
+    shortcut_config: &mut ShortcutConfig,
+    pack: PromptPack,
+    strategy: ImportStrategy,
+) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for mut prompt in pack.prompts {
+        if let Some(existing) = prompt_config.prompts.iter_mut().find(|p| p.id == prompt.id) {
+            report.conflicts.push(prompt.id.clone());
+            match strategy {
+                ImportStrategy::Merge => report.skipped_prompts.push(prompt.id),
+                ImportStrategy::Overwrite => {
+                    // 保留本地排序，避免打乱用户调整过的顺序
+                    prompt.sort_order = existing.sort_order;
+                    report.updated_prompts.push(prompt.id.clone());
+                    *existing = prompt;
+                }
+            }
+            continue;
+        }
+
+        if prompt_config.prompts.len() >= prompt_config.max_prompts as usize {
+            report.limit_reached = true;
+            report.skipped_prompts.push(prompt.id);
+            continue;
+        }
+
+        prompt.sort_order = prompt_config.prompts.iter().map(|p| p.sort_order).max().unwrap_or(0) + 1;
+        report.added_prompts.push(prompt.id.clone());
+        prompt_config.prompts.push(prompt);
+    }
+
+    for (id, binding) in pack.shortcuts {
+        if shortcut_config.shortcuts.contains_key(&id) {
+            report.conflicts.push(id.clone());
+            match strategy {
+                ImportStrategy::Merge => {
+                    report.skipped_shortcuts.push(id);
+                    continue;
+                }
+                ImportStrategy::Overwrite => report.updated_shortcuts.push(id.clone()),
+            }
+        } else {
+            report.added_shortcuts.push(id.clone());
+        }
+        shortcut_config.shortcuts.insert(id, binding);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{default_custom_prompt_config, default_shortcut_config};
+
+    #[test]
+    fn test_round_trip_and_conflicts() {
+        let prompt_config = default_custom_prompt_config();
+        let shortcut_config = default_shortcut_config();
+        let first_id = prompt_config.prompts[0].id.clone();
+
+        for format in [PackFormat::Json, PackFormat::Toml] {
+            let content = export_pack(&prompt_config, &shortcut_config, std::slice::from_ref(&first_id), &[], None, format).unwrap();
+            let pack = parse_pack(&content).unwrap();
+            assert_eq!(pack.prompts.len(), 1);
+            assert_eq!(pack.shortcuts.len(), shortcut_config.shortcuts.len());
+
+            let mut prompts = prompt_config.clone();
+            let mut shortcuts = shortcut_config.clone();
+            let report = apply_pack(&mut prompts, &mut shortcuts, pack.clone(), ImportStrategy::Merge);
+            assert_eq!(report.skipped_prompts, vec![first_id.clone()]);
+            assert!(report.conflicts.contains(&first_id));
+
+            // 新ID在达到上限时被跳过
+            let mut renamed = pack;
+            renamed.prompts[0].id = "imported".to_string();
+            prompts.max_prompts = prompts.prompts.len() as u32;
+            let report = apply_pack(&mut prompts, &mut shortcuts, renamed, ImportStrategy::Overwrite);
+            assert!(report.limit_reached);
+            assert!(report.added_prompts.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_rejects_newer_version() {
+        assert!(parse_pack(r#"{"version": 99, "prompts": []}"#).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Mutex;
-use crate::constants::{window, theme, audio, mcp, telegram, font};
+use crate::constants::{window, theme, audio, mcp, telegram, font, enhance};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
@@ -19,6 +19,16 @@ pub struct AppConfig {
     pub custom_prompt_config: CustomPromptConfig, // 自定义prompt配置
     #[serde(default = "default_shortcut_config")]
     pub shortcut_config: ShortcutConfig, // 自定义快捷键配置
+    #[serde(default = "default_policy_config")]
+    pub policy_config: PolicyConfig, // 自动回复策略配置
+    #[serde(default = "default_routing_config")]
+    pub routing_config: RoutingConfig, // 免打扰和交互路由配置
+    #[serde(default = "default_enhance_config")]
+    pub enhance_config: EnhanceConfig, // 提示词增强模板配置
+    #[serde(default = "default_locale")]
+    pub locale: String, // 服务端文案语言："zh-CN" | "en"
+    #[serde(default = "default_memory_config")]
+    pub memory_config: MemoryConfig, // 记忆存储配置
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -140,6 +150,8 @@ pub struct CustomPrompt {
     pub template_false: Option<String>,    // 开关为false时的模板
     #[serde(default = "default_prompt_state")]
     pub current_state: bool,               // 当前开关状态（原default_state）
+    #[serde(default)]
+    pub append_to_response: bool,          // 普通prompt是否追加到每次回复
 }
 
 // 自定义prompt配置
@@ -151,6 +163,8 @@ pub struct CustomPromptConfig {
     pub enabled: bool,
     #[serde(default = "default_custom_prompt_max_prompts")]
     pub max_prompts: u32,
+    #[serde(default = "default_compose_responses")]
+    pub compose_responses: bool, // 是否由寸止进程把上下文追加内容合成到回复中
 }
 
 // 快捷键配置
@@ -182,6 +196,135 @@ pub struct ShortcutKey {
     pub meta: bool, // macOS的Cmd键
 }
 
+// 自动回复策略配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PolicyConfig {
+    #[serde(default = "default_policy_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>, // 按顺序匹配，第一条命中的规则生效
+}
+
+// 自动回复规则，所有已设置的条件都满足时命中
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PolicyRule {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_policy_rule_enabled")]
+    pub enabled: bool,
+    pub message_pattern: Option<String>,      // 消息内容正则
+    pub project_path: Option<String>,         // 项目路径前缀或glob
+    pub options_contain: Option<Vec<String>>, // 预定义选项需包含的全部选项
+    pub time_start: Option<String>,           // 生效开始时间 "HH:MM"
+    pub time_end: Option<String>,             // 生效结束时间 "HH:MM"，早于开始时间表示跨天
+    pub action: PolicyAction,
+    pub option: Option<String>,               // select_option 时自动选择的选项
+}
+
+// 自动回复规则命中后的动作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    Ask,
+    AutoContinue,
+    SelectOption,
+    // 无法识别的动作，读取旧配置时不让整个配置文件解析失败，命中时按询问处理
+    #[serde(other)]
+    Unknown,
+}
+
+// 免打扰和交互路由配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoutingConfig {
+    #[serde(default = "default_routing_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub schedules: Vec<RoutingSchedule>, // 按顺序匹配，第一条命中的时间段生效
+    #[serde(default)]
+    pub idle_to_telegram_minutes: Option<u32>, // 桌面空闲超过N分钟时改用Telegram
+}
+
+// 时间段路由规则
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoutingSchedule {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_routing_schedule_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub weekdays: Vec<u32>,         // 1=周一 ... 7=周日，为空表示每天
+    pub time_start: Option<String>, // 开始时间 "HH:MM"
+    pub time_end: Option<String>,   // 结束时间 "HH:MM"，早于开始时间表示跨天
+    pub backend: Option<String>,    // "popup" | "telegram" | "both"，为空表示沿用默认
+    #[serde(default)]
+    pub mute_audio: bool,           // 是否静音提示音
+}
+
+// 提示词增强模板配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnhanceConfig {
+    #[serde(default = "default_enhance_active_template")]
+    pub active_template: String, // 当前使用的模板ID
+    #[serde(default = "default_enhance_templates")]
+    pub templates: Vec<EnhanceTemplate>,
+    #[serde(default = "default_enhance_mode")]
+    pub mode: String, // "agent" 发回给AI处理 | "llm" 调用配置的模型直接改写
+    #[serde(default = "default_enhance_llm_config")]
+    pub llm: EnhanceLlmConfig,
+}
+
+// OpenAI兼容的模型接口配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnhanceLlmConfig {
+    #[serde(default = "default_enhance_llm_base_url")]
+    pub base_url: String, // 如 http://127.0.0.1:11434/v1
+    #[serde(default)]
+    pub api_key: String, // 本地模型服务可留空
+    #[serde(default)]
+    pub model: String,
+    #[serde(default = "default_enhance_llm_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+// 增强模板，支持 {{original_text}}、{{selected_options}}、{{project_path}}、{{memories}} 变量
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnhanceTemplate {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+}
+
+// 记忆存储配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryConfig {
+    #[serde(default)]
+    pub team_dir: String, // 团队共享记忆目录，留空表示不启用团队作用域
+    #[serde(default = "default_memory_storage")]
+    pub storage: String, // 新项目的记忆存储位置："project" 仓库内 | "app_data" 应用数据目录，迁移过的项目按登记的位置
+    #[serde(default)]
+    pub categories: Vec<MemoryCategoryDef>, // 自定义记忆分类，对所有项目生效
+    #[serde(default = "default_archive_context_days")]
+    pub archive_context_days: u32, // 整理时归档超过该天数未更新的上下文记忆，0 表示不归档
+    #[serde(default = "default_recall_max_tokens")]
+    pub recall_max_tokens: usize, // 回忆输出的默认 token 预算，0 表示不限制
+    #[serde(default = "default_secret_policy")]
+    pub secret_policy: String, // 记忆内容中发现疑似密钥时的处理方式："mask" 遮盖后写入并提示 | "reject" 拒绝写入
+    #[serde(default)]
+    pub category_ttl_days: HashMap<String, u32>, // 各分类新记忆的默认有效期（天），如 {"context": 30}，未配置或为 0 表示永不过期
+}
+
+// 记忆分类定义，项目内的 categories.json 使用同样的结构
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MemoryCategoryDef {
+    pub key: String, // 工具参数中使用的标识，如 "security"
+    pub title: String, // 分类文件标题
+    pub filename: String, // 记忆文件名，如 "security.md"
+    #[serde(default)]
+    pub priority: i32, // 召回优先级，越大越靠前
+    #[serde(default)]
+    pub label: String, // 召回总览中的简称，留空时使用标题
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TelegramConfig {
     #[serde(default = "default_telegram_enabled")]
@@ -215,6 +358,11 @@ impl Default for AppConfig {
             telegram_config: default_telegram_config(),
             custom_prompt_config: default_custom_prompt_config(),
             shortcut_config: default_shortcut_config(),
+            policy_config: default_policy_config(),
+            routing_config: default_routing_config(),
+            enhance_config: default_enhance_config(),
+            locale: default_locale(),
+            memory_config: default_memory_config(),
         }
     }
 }
@@ -274,9 +422,14 @@ pub fn default_custom_prompt_config() -> CustomPromptConfig {
         prompts: default_custom_prompts(),
         enabled: default_custom_prompt_enabled(),
         max_prompts: default_custom_prompt_max_prompts(),
+        compose_responses: default_compose_responses(),
     }
 }
 
+pub fn default_compose_responses() -> bool {
+    true
+}
+
 pub fn default_always_on_top() -> bool {
     window::DEFAULT_ALWAYS_ON_TOP
 }
@@ -492,6 +645,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: None,
             template_false: None,
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_2".to_string(),
@@ -506,6 +660,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: None,
             template_false: None,
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_3".to_string(),
@@ -520,6 +675,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: None,
             template_false: None,
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_4".to_string(),
@@ -534,6 +690,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: None,
             template_false: None,
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_5".to_string(),
@@ -548,6 +705,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: None,
             template_false: None,
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_6".to_string(),
@@ -562,6 +720,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: None,
             template_false: None,
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_7".to_string(),
@@ -576,6 +735,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: Some("✔️请记住，帮我生成总结性Markdown文档".to_string()),
             template_false: Some("❌请记住，不要生成总结性Markdown文档".to_string()),
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_8".to_string(),
@@ -590,6 +750,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: Some("✔️请记住，帮我生成测试脚本".to_string()),
             template_false: Some("❌请记住，不要生成测试脚本".to_string()),
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_9".to_string(),
@@ -604,6 +765,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: Some("✔️请记住，帮我编译".to_string()),
             template_false: Some("❌请记住，不要编译，用户自己编译".to_string()),
             current_state: false,
+            append_to_response: false,
         },
         CustomPrompt {
             id: "default_10".to_string(),
@@ -618,6 +780,7 @@ pub fn default_custom_prompts() -> Vec<CustomPrompt> {
             template_true: Some("✔️请记住，帮我运行".to_string()),
             template_false: Some("❌请记住，不要运行，用户自己运行".to_string()),
             current_state: false,
+            append_to_response: false,
         },
     ]
 }
@@ -694,4 +857,117 @@ pub fn default_shortcuts() -> HashMap<String, ShortcutBinding> {
     shortcuts
 }
 
+// 自动回复策略默认值函数
+pub fn default_policy_config() -> PolicyConfig {
+    PolicyConfig {
+        enabled: default_policy_enabled(),
+        rules: Vec::new(),
+    }
+}
+
+pub fn default_policy_enabled() -> bool {
+    false
+}
+
+pub fn default_policy_rule_enabled() -> bool {
+    true
+}
 
+// 免打扰和路由默认值函数
+pub fn default_routing_config() -> RoutingConfig {
+    RoutingConfig {
+        enabled: default_routing_enabled(),
+        schedules: Vec::new(),
+        idle_to_telegram_minutes: None,
+    }
+}
+
+pub fn default_routing_enabled() -> bool {
+    false
+}
+
+pub fn default_routing_schedule_enabled() -> bool {
+    true
+}
+
+// 提示词增强模板默认值函数
+pub fn default_enhance_config() -> EnhanceConfig {
+    EnhanceConfig {
+        active_template: default_enhance_active_template(),
+        templates: default_enhance_templates(),
+        mode: default_enhance_mode(),
+        llm: default_enhance_llm_config(),
+    }
+}
+
+pub fn default_enhance_mode() -> String {
+    enhance::MODE_AGENT.to_string()
+}
+
+pub fn default_enhance_llm_config() -> EnhanceLlmConfig {
+    EnhanceLlmConfig {
+        base_url: default_enhance_llm_base_url(),
+        api_key: String::new(),
+        model: String::new(),
+        timeout_secs: default_enhance_llm_timeout_secs(),
+    }
+}
+
+pub fn default_enhance_llm_base_url() -> String {
+    enhance::DEFAULT_LLM_BASE_URL.to_string()
+}
+
+pub fn default_enhance_llm_timeout_secs() -> u64 {
+    enhance::DEFAULT_LLM_TIMEOUT_SECS
+}
+
+pub fn default_enhance_active_template() -> String {
+    enhance::TEMPLATE_DEFAULT_EN.to_string()
+}
+
+pub fn default_enhance_templates() -> Vec<EnhanceTemplate> {
+    vec![
+        EnhanceTemplate {
+            id: enhance::TEMPLATE_DEFAULT_EN.to_string(),
+            name: "English".to_string(),
+            content: enhance::DEFAULT_TEMPLATE_EN.to_string(),
+        },
+        EnhanceTemplate {
+            id: enhance::TEMPLATE_DEFAULT_ZH.to_string(),
+            name: "中文".to_string(),
+            content: enhance::DEFAULT_TEMPLATE_ZH.to_string(),
+        },
+    ]
+}
+
+pub fn default_locale() -> String {
+    "zh-CN".to_string()
+}
+
+pub fn default_memory_config() -> MemoryConfig {
+    MemoryConfig {
+        team_dir: String::new(),
+        storage: default_memory_storage(),
+        categories: Vec::new(),
+        archive_context_days: default_archive_context_days(),
+        recall_max_tokens: default_recall_max_tokens(),
+        secret_policy: default_secret_policy(),
+        category_ttl_days: HashMap::new(),
+    }
+}
+
+pub fn default_memory_storage() -> String {
+    "project".to_string()
+}
+
+pub fn default_archive_context_days() -> u32 {
+    90
+}
+
+pub fn default_recall_max_tokens() -> usize {
+    2000
+}
+
+pub fn default_secret_policy() -> String {
+    "mask".to_string()
+}
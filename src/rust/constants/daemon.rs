@@ -0,0 +1,25 @@
+// 常驻进程（daemon）相关常量
+
+/// 启动常驻模式的命令行参数
+pub const CLI_FLAG: &str = "--daemon";
+
+/// daemon 连接信息文件名（位于配置目录下）
+pub const INFO_FILE_NAME: &str = "daemon.json";
+
+/// 监听地址（仅本机回环）
+pub const LISTEN_HOST: &str = "127.0.0.1";
+
+/// 连接 daemon 的超时时间 (ms)
+pub const CONNECT_TIMEOUT_MS: u64 = 500;
+
+/// daemon 读取请求行的超时时间 (ms)
+pub const REQUEST_READ_TIMEOUT_MS: u64 = 5000;
+
+/// 等待用户回答期间检查寸止是否断开连接的间隔 (ms)
+pub const HANGUP_POLL_INTERVAL_MS: u64 = 500;
+
+/// 请求处理完成事件（通知前端重置弹窗状态）
+pub const EVENT_REQUEST_COMPLETED: &str = "mcp-request-completed";
+
+/// 新请求事件（前端已有监听）
+pub const EVENT_NEW_REQUEST: &str = "mcp-request";
@@ -0,0 +1,85 @@
+// 提示词增强模板相关常量
+
+/// 英文默认模板ID
+pub const TEMPLATE_DEFAULT_EN: &str = "default_en";
+
+/// 中文默认模板ID
+pub const TEMPLATE_DEFAULT_ZH: &str = "default_zh";
+
+/// 增强模式：把增强提示词发回给AI处理
+pub const MODE_AGENT: &str = "agent";
+
+/// 增强模式：调用配置的模型接口直接改写
+pub const MODE_LLM: &str = "llm";
+
+/// 默认模型接口地址（OpenAI兼容，本地模型服务）
+pub const DEFAULT_LLM_BASE_URL: &str = "http://127.0.0.1:11434/v1";
+
+/// 默认模型请求超时时间（秒）
+pub const DEFAULT_LLM_TIMEOUT_SECS: u64 = 60;
+
+/// 模型请求最大重试次数
+pub const LLM_MAX_RETRIES: usize = 3;
+
+/// 增强结果标签
+pub const ENHANCED_TAG_START: &str = "<augment-enhanced-prompt>";
+pub const ENHANCED_TAG_END: &str = "</augment-enhanced-prompt>";
+
+/// 直接改写时的系统提示词
+pub const LLM_SYSTEM_PROMPT: &str = "You rewrite a user's draft instruction for an AI coding agent. Do not call any tools. Reply only with the enhanced instruction wrapped in <augment-enhanced-prompt></augment-enhanced-prompt>.";
+
+/// 直接改写时发送给模型的模板
+///
+/// 模型接口只收到这一条消息，没有对话历史也不能调用工具，不能沿用面向AI对话的增强模板
+pub const LLM_TEMPLATE: &str = r#"Rewrite the draft instruction in 《》 so that it is clearer, more specific and less ambiguous, and correct any mistakes. Keep the original intent and reply in the same language as the draft. Use the selected options and project memories below only as background.
+
+Selected options: {{selected_options}}
+
+Project memories:
+{{memories}}
+
+Draft instruction:
+
+《{{original_text}}》"#;
+
+/// 原始输入变量
+pub const VAR_ORIGINAL_TEXT: &str = "{{original_text}}";
+
+/// 已选选项变量
+pub const VAR_SELECTED_OPTIONS: &str = "{{selected_options}}";
+
+/// 项目路径变量
+pub const VAR_PROJECT_PATH: &str = "{{project_path}}";
+
+/// 当前项目记忆变量
+pub const VAR_MEMORIES: &str = "{{memories}}";
+
+/// 英文默认增强模板
+pub const DEFAULT_TEMPLATE_EN: &str = r#"Use the following prompt to optimize and enhance the context of the content in 《》, and return the enhanced result by calling the tool '寸止' after completion.Here is an instruction that I'd like to give you, but it needs to be improved. Rewrite and enhance this instruction to make it clearer, more specific, less ambiguous, and correct any mistakes. Reply immediately with your answer, even if you're not sure. Consider the context of our conversation history when enhancing the prompt. Reply with the following format:
+
+### BEGIN RESPONSE ###
+Here is an enhanced version of the original instruction that is more specific and clear:
+<augment-enhanced-prompt>enhanced prompt goes here</augment-enhanced-prompt>
+
+### END RESPONSE ###
+
+Here is my original instruction:
+
+《{{original_text}}》"#;
+
+/// 中文默认增强模板
+pub const DEFAULT_TEMPLATE_ZH: &str = r#"请优化并增强《》中的内容，完成后调用工具「寸止」返回增强结果。下面是我想给你的一条指令，但它还不够好。请改写这条指令，使其更清晰、更具体、更少歧义，并修正其中的错误。即使不确定也请立即回复。增强时请结合我们的对话历史和以下项目记忆：
+
+{{memories}}
+
+请按以下格式回复：
+
+### BEGIN RESPONSE ###
+以下是更具体、更清晰的增强版指令：
+<augment-enhanced-prompt>增强后的指令</augment-enhanced-prompt>
+
+### END RESPONSE ###
+
+我的原始指令：
+
+《{{original_text}}》"#;
@@ -3,6 +3,8 @@
 
 pub mod app;
 pub mod audio;
+pub mod daemon;
+pub mod enhance;
 pub mod font;
 pub mod mcp;
 pub mod network;
@@ -22,6 +24,8 @@ pub use validation::*;
 pub use window::*;
 
 // 这些模块有重复的常量名，使用模块限定访问
+// pub use daemon::*;
+// pub use enhance::*;
 // pub use mcp::*;
 // pub use network::*;
 // pub use telegram::*;
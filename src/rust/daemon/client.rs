@@ -0,0 +1,55 @@
+use anyhow::Result;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use super::read_daemon_info;
+use super::types::DaemonRequest;
+use crate::constants::daemon;
+use crate::mcp::types::PopupRequest;
+use crate::{log_debug, log_important};
+
+/// 尝试将弹窗请求交给正在运行的 daemon 处理
+///
+/// daemon 未运行时返回 `Ok(None)`，调用方应回退到启动独立 UI 进程
+pub fn try_send_to_daemon(request: &PopupRequest) -> Result<Option<String>> {
+    let info = match read_daemon_info() {
+        Ok(Some(info)) => info,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            log_debug!("读取daemon连接信息失败，回退到独立进程: {}", e);
+            return Ok(None);
+        }
+    };
+
+    let addr: SocketAddr = format!("{}:{}", daemon::LISTEN_HOST, info.port).parse()?;
+    let mut stream = match TcpStream::connect_timeout(&addr, Duration::from_millis(daemon::CONNECT_TIMEOUT_MS)) {
+        Ok(stream) => stream,
+        Err(e) => {
+            // 连接信息残留但 daemon 已退出
+            log_debug!("连接daemon失败（pid: {}），回退到独立进程: {}", info.pid, e);
+            return Ok(None);
+        }
+    };
+
+    let payload = DaemonRequest {
+        token: info.token,
+        request: request.clone(),
+    };
+    stream.write_all(payload.to_line()?.as_bytes())?;
+    stream.flush()?;
+
+    log_important!(info, "请求已发送到daemon: {}", request.id);
+
+    // 等待用户回答，不设置读取超时
+    let mut reader = BufReader::new(stream);
+    let mut response = String::new();
+    reader.read_line(&mut response)?;
+
+    let response = response.trim();
+    if response.is_empty() {
+        anyhow::bail!("daemon在返回响应前关闭了连接");
+    }
+
+    Ok(Some(response.to_string()))
+}
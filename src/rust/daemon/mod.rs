@@ -0,0 +1,73 @@
+//! 等一下常驻模式模块
+//!
+//! 等一下以 `--daemon` 启动后常驻托盘，寸止通过本机回环连接发送弹窗请求，
+//! 避免每次请求都冷启动完整的 Tauri 应用
+
+pub mod client;
+pub mod server;
+pub mod types;
+
+pub use client::try_send_to_daemon;
+pub use server::{start_daemon_server, setup_daemon_tray};
+pub use types::{DaemonInfo, DaemonRequest};
+
+use anyhow::Result;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::constants::daemon;
+
+/// 检查当前进程是否以常驻模式运行
+pub fn is_daemon_mode() -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    args.len() >= 2 && args[1] == daemon::CLI_FLAG
+}
+
+/// 获取 daemon 连接信息文件路径
+pub fn get_daemon_info_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("无法获取配置目录"))?
+        .join("cunzhi");
+
+    fs::create_dir_all(&config_dir)?;
+
+    Ok(config_dir.join(daemon::INFO_FILE_NAME))
+}
+
+/// 读取 daemon 连接信息，文件不存在时返回 None
+pub fn read_daemon_info() -> Result<Option<DaemonInfo>> {
+    let info_path = get_daemon_info_path()?;
+    if !info_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&info_path)?;
+    let info: DaemonInfo = serde_json::from_str(&content)?;
+    Ok(Some(info))
+}
+
+/// 写入 daemon 连接信息，文件包含连接令牌，只允许当前用户读写
+pub fn write_daemon_info(info: &DaemonInfo) -> Result<()> {
+    let info_path = get_daemon_info_path()?;
+    // 重新创建文件，避免沿用残留文件的宽松权限
+    let _ = fs::remove_file(&info_path);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&info_path)?;
+    file.write_all(serde_json::to_string_pretty(info)?.as_bytes())?;
+    Ok(())
+}
+
+/// 删除 daemon 连接信息文件（daemon 退出时调用）
+pub fn remove_daemon_info() {
+    if let Ok(info_path) = get_daemon_info_path() {
+        let _ = fs::remove_file(info_path);
+    }
+}
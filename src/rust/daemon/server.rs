@@ -0,0 +1,200 @@
+use anyhow::Result;
+use std::io::{BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+use super::types::{DaemonInfo, DaemonRequest};
+use super::{remove_daemon_info, write_daemon_info};
+use crate::config::AppState;
+use crate::constants::daemon;
+use crate::log_important;
+
+/// 启动 daemon 监听
+///
+/// 绑定本机随机端口并写入连接信息文件，每个连接在单独的线程中读取请求，
+/// 同一时间只展示一个弹窗，其余请求排队等待
+pub fn start_daemon_server(app_handle: &AppHandle) -> Result<()> {
+    let listener = TcpListener::bind((daemon::LISTEN_HOST, 0))?;
+    let port = listener.local_addr()?.port();
+
+    let info = DaemonInfo {
+        port,
+        token: uuid::Uuid::new_v4().to_string(),
+        pid: std::process::id(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+
+    write_daemon_info(&info)?;
+
+    log_important!(info, "daemon已启动，监听端口: {}", port);
+
+    let app_handle = app_handle.clone();
+    let token: Arc<str> = info.token.into();
+    let popup_lock = Arc::new(Mutex::new(()));
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app_handle = app_handle.clone();
+                    let token = Arc::clone(&token);
+                    let popup_lock = Arc::clone(&popup_lock);
+                    // 卡住的连接只占用自己的线程，不会阻塞其他弹窗
+                    std::thread::spawn(move || {
+                        if let Err(e) = handle_connection(&app_handle, stream, &token, &popup_lock) {
+                            log_important!(warn, "处理daemon请求失败: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    log_important!(warn, "接受daemon连接失败: {}", e);
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// 处理单个寸止连接：展示弹窗并等待用户回答，寸止断开连接时关闭弹窗
+fn handle_connection(app_handle: &AppHandle, stream: TcpStream, token: &str, popup_lock: &Mutex<()>) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_millis(daemon::REQUEST_READ_TIMEOUT_MS)))?;
+    let daemon_request = DaemonRequest::read_from(&mut BufReader::new(stream.try_clone()?))?;
+    if daemon_request.token != token {
+        anyhow::bail!("daemon令牌校验失败，拒绝请求");
+    }
+
+    let request = daemon_request.request;
+    log_important!(info, "daemon收到弹窗请求: {}", request.id);
+
+    // 之后的读取只用于检测寸止是否断开
+    stream.set_read_timeout(Some(Duration::from_millis(daemon::HANGUP_POLL_INTERVAL_MS)))?;
+    let _popup = popup_lock
+        .lock()
+        .map_err(|e| anyhow::anyhow!("获取弹窗锁失败: {}", e))?;
+    if peer_closed(&stream) {
+        log_important!(info, "寸止在排队期间断开了连接，跳过弹窗: {}", request.id);
+        return Ok(());
+    }
+
+    // 复用非MCP模式下的响应通道，send_mcp_response 会把回答发到这里
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    {
+        let state = app_handle.state::<AppState>();
+        let mut channel = state
+            .response_channel
+            .lock()
+            .map_err(|e| anyhow::anyhow!("获取响应通道失败: {}", e))?;
+        *channel = Some(sender);
+    }
+
+    app_handle.emit(daemon::EVENT_NEW_REQUEST, &request)?;
+    show_main_window(app_handle);
+
+    let Some(response) = wait_for_response(&stream, receiver) else {
+        log_important!(info, "寸止已断开连接，关闭弹窗: {}", request.id);
+        cancel_pending_request(app_handle);
+        hide_main_window(app_handle);
+        return Ok(());
+    };
+
+    let mut writer = stream;
+    writer.write_all(response.as_bytes())?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// 等待用户回答，寸止在回答前断开连接时返回 None
+fn wait_for_response(stream: &TcpStream, mut receiver: oneshot::Receiver<String>) -> Option<String> {
+    loop {
+        match receiver.try_recv() {
+            Ok(response) => return Some(response),
+            // 通道被丢弃（例如窗口被关闭）时视为用户取消
+            Err(TryRecvError::Closed) => return Some("CANCELLED".to_string()),
+            Err(TryRecvError::Empty) => {}
+        }
+        if peer_closed(stream) {
+            return None;
+        }
+    }
+}
+
+/// 检查寸止是否已断开连接，连接正常时最多阻塞一个读取超时
+fn peer_closed(stream: &TcpStream) -> bool {
+    match stream.peek(&mut [0u8; 1]) {
+        Ok(0) => true,
+        // 寸止发送请求后不会再发送数据，收到多余数据时只等待下一轮检查
+        Ok(_) => {
+            std::thread::sleep(Duration::from_millis(daemon::HANGUP_POLL_INTERVAL_MS));
+            false
+        }
+        Err(e) => !matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted),
+    }
+}
+
+/// 显示并聚焦主窗口
+pub fn show_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// 回答完成后隐藏主窗口，并通知前端重置弹窗状态
+pub fn hide_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.hide();
+    }
+
+    if let Err(e) = app_handle.emit(daemon::EVENT_REQUEST_COMPLETED, ()) {
+        log_important!(warn, "发送请求完成事件失败: {}", e);
+    }
+}
+
+/// 取消当前等待中的请求（窗口被关闭时调用）
+pub fn cancel_pending_request(app_handle: &AppHandle) {
+    let state = app_handle.state::<AppState>();
+    let sender = match state.response_channel.lock() {
+        Ok(mut channel) => channel.take(),
+        Err(_) => None,
+    };
+
+    if let Some(sender) = sender {
+        let _ = sender.send("CANCELLED".to_string());
+    }
+}
+
+/// 设置托盘图标和菜单
+pub fn setup_daemon_tray(app_handle: &AppHandle) -> Result<()> {
+    let show_item = MenuItem::with_id(app_handle, "show", "打开设置", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app_handle, "quit", "退出", true, None::<&str>)?;
+    let menu = Menu::with_items(app_handle, &[&show_item, &quit_item])?;
+
+    let mut builder = TrayIconBuilder::with_id("cunzhi-daemon")
+        .tooltip("寸止")
+        .menu(&menu)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show" => show_main_window(app),
+            "quit" => {
+                cancel_pending_request(app);
+                remove_daemon_info();
+                app.exit(0);
+            }
+            _ => {}
+        });
+
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app_handle)?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+
+use crate::mcp::types::PopupRequest;
+
+/// daemon 连接信息（写入配置目录，供寸止发现正在运行的 daemon）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonInfo {
+    /// 监听端口
+    pub port: u16,
+    /// 连接令牌，防止其他本机进程冒充寸止发送请求
+    pub token: String,
+    /// daemon 进程ID
+    pub pid: u32,
+    /// daemon 版本
+    pub version: String,
+}
+
+/// 寸止发送给 daemon 的请求（单行 JSON）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DaemonRequest {
+    pub token: String,
+    pub request: PopupRequest,
+}
+
+impl DaemonRequest {
+    /// 编码为以换行结尾的单行 JSON
+    pub fn to_line(&self) -> Result<String> {
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        Ok(line)
+    }
+
+    /// 读取一行请求，读取超时或对方在发送完整请求前断开时返回错误
+    pub fn read_from(reader: &mut impl BufRead) -> Result<Self> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            anyhow::bail!("寸止在发送请求前关闭了连接");
+        }
+        Ok(serde_json::from_str(line.trim())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::time::Duration;
+
+    #[test]
+    fn test_request_line_round_trip_over_loopback() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let request = DaemonRequest {
+            token: "token".to_string(),
+            request: PopupRequest {
+                id: "req-1".to_string(),
+                message: "第一行\n第二行".to_string(),
+                predefined_options: Some(vec!["继续".to_string()]),
+                is_markdown: true,
+                project_path: None,
+                route: None,
+            },
+        };
+        let line = request.to_line().unwrap();
+        assert_eq!(line.matches('\n').count(), 1);
+
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(line.as_bytes()).unwrap();
+            // 不发送请求就挂起的客户端
+            TcpStream::connect(addr).unwrap()
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let received = DaemonRequest::read_from(&mut BufReader::new(stream)).unwrap();
+        assert_eq!(received.token, "token");
+        assert_eq!(received.request.message, "第一行\n第二行");
+        assert_eq!(received.request.predefined_options, Some(vec!["继续".to_string()]));
+
+        let _stuck = client.join().unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        stream.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+        assert!(DaemonRequest::read_from(&mut BufReader::new(stream)).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use super::Msg;
+
+/// English messages
+pub fn get(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ServerInstructions => "Zhi code review assistant with interactive dialogs and memory management",
+        Msg::ZhiDescription => "Interactive code review tool supporting predefined options, free-text input and image upload",
+        Msg::ZhiParamMessage => "Message to show to the user",
+        Msg::ZhiParamOptions => "List of predefined options (optional)",
+        Msg::ZhiParamMarkdown => "Whether the message is Markdown, defaults to true",
+        Msg::ZhiParamProjectPath => "Current project path (optional), used to match auto-reply policies",
+        Msg::JiDescription => "Global memory tool for storing and managing development rules, user preferences and best practices",
+        Msg::JiParamAction => "Action: 记忆/add (add a memory), 回忆/recall (get project information), 列表/list (list memories with ids), 更新/update (update by id), 删除/delete (delete by id), 移动/move (move by id to another category), 搜索/search (rank memories by relevance), 整理/organize (remove expired memories, merge duplicates, normalize whitespace and archive stale context; previews before applying), 导入/import (import memories from other agents' rule files), 导出/export (write memories into a managed block of other agents' rule files), 提升/promote (make a branch memory apply to all branches by id), 历史/history (show the add, update and delete change log), 撤销/undo (revert one change by change id)",
+        Msg::JiParamProjectPath => "Project path (required)",
+        Msg::JiParamContent => "Memory content (required for add and update)",
+        Msg::JiParamCategory => "Memory category: built-in rule, preference, pattern, context, or a custom category declared in config or the project categories.json. Defaults to context when adding; the target category when moving; a filter when listing",
+        Msg::JiParamId => "Memory id (required for update, delete, move and promote; use list to look it up). Optional for history to show one memory's changes. For undo, pass the change id returned by history",
+        Msg::JiParamQuery => "Search query (required for search; optional for recall to return only relevant memories)",
+        Msg::JiParamLimit => "Maximum number of results for search and recall (defaults to 10) and for history (defaults to 20)",
+        Msg::JiParamOnDuplicate => "How to handle a similar existing memory when adding: reject (return the existing id, default), merge (merge into the existing memory), allow (add anyway)",
+        Msg::JiParamScope => "Memory scope: project, team (shared directory) or global (per user). Writes default to project; reads without a scope merge project > team > global",
+        Msg::JiParamTags => "Tags: attached when adding, replace existing tags when updating (an empty array clears them), and when listing, searching or recalling only memories with all given tags are returned",
+        Msg::JiParamPlanId => "Organize plan id: without it the organize action only previews the diff; pass the plan id returned by the preview to rewrite the files",
+        Msg::JiParamRulesFile => "Rule file: claude (CLAUDE.md), agents (AGENTS.md), cursor (.cursorrules), copilot (.github/copilot-instructions.md), windsurf (.windsurfrules). Optional for import and export; defaults to every rule file that exists in the project",
+        Msg::JiParamMaxChars => "Maximum characters of recall output (optional for recall), 0 means unlimited",
+        Msg::JiParamMaxTokens => "Maximum tokens of recall output (optional for recall); the configured default applies when neither max_chars nor max_tokens is given, 0 means unlimited",
+        Msg::JiParamTtl => "How long the memory stays valid (optional for add and update), e.g. 12h, 7d, 2w; never means it does not expire. Defaults to the category's configured TTL. Expired memories are no longer recalled or searched and are removed by organize",
+        Msg::JiParamExpiresAt => "When the memory expires (optional for add and update), as YYYY-MM-DD or RFC 3339; use either this or ttl",
+        Msg::JiParamBranch => "Git branch the memory applies to (optional when adding); use current for the checked-out branch. Branch memories are only recalled and searched while that branch is checked out",
+        Msg::SouDescription => "Search a project for code context relevant to a query. The index is updated incrementally before each search so results are always current. Returns formatted snippets semantically related to the query.",
+        Msg::SouParamProjectRoot => "Absolute path of the project root using forward slashes (/). Example: C:/Users/username/projects/myproject",
+        Msg::SouParamQuery => "Natural-language query used to find relevant code. The tool runs a semantic search and returns matching snippets. Examples: 'logging setup initialize logger', 'user authentication login', 'database connection pool', 'error handling exceptions', 'API endpoint routes'. Results are formatted snippets with file paths and line numbers.",
+        Msg::JiDisabled => "The memory tool is disabled",
+        Msg::SouDisabled => "The code search tool is disabled",
+        Msg::UnknownTool => "Unknown tool: {}",
+        Msg::ParamParseFailed => "Failed to parse arguments: {}",
+
+        Msg::UserCancelled => "The user cancelled the operation",
+        Msg::NoContent => "The user did not provide any content",
+        Msg::SelectedOptions => "Selected options: {}",
+        Msg::ImageTitle => "=== Image {} ===",
+        Msg::ImageFilename => "File name: {}",
+        Msg::ImageType => "Type: {}",
+        Msg::ImageSize => "Size: {}",
+        Msg::ImagePreview => "Base64 preview: {}",
+        Msg::ImageBase64Length => "Full Base64 length: {} characters",
+        Msg::ImageNote => "💡 Note: the user provided {} image(s). If the assistant cannot display images, the data is included in the Base64 information above.",
+
+        Msg::JiPathInvalid => "Path validation failed: {}\nOriginal path: {}\nCheck the path format; Windows paths need a proper drive letter (e.g. C:\\path)",
+        Msg::JiOpenFailed => "Failed to open memory: {}",
+        Msg::JiMissingContent => "Memory content is required",
+        Msg::JiMissingQuery => "A search query is required",
+        Msg::JiMissingId => "A memory id is required; use the list action to look it up",
+        Msg::JiMissingChangeId => "A change id is required; use the history action to look it up",
+        Msg::JiUpdateMissingFields => "Update needs new content, category, tags or an expiry",
+        Msg::JiMoveMissingCategory => "Move needs a target category",
+        Msg::JiUnknownCategory => "Unknown memory category: {}, expected one of: {}",
+        Msg::JiUnknownRulesFile => "Unknown rule file: {}, expected one of: {}",
+        Msg::JiNoRulesFile => "No rule files found in the project",
+        Msg::JiNoRulesFileToExport => "No rule files found in the project; pass rules_file to choose the file to create",
+        Msg::JiUnknownBranch => "Cannot determine the current branch, the project may be in detached HEAD state; pass the branch name instead",
+        Msg::JiAddFailed => "Failed to add memory: {}",
+        Msg::JiRecallFailed => "Failed to recall project information: {}",
+        Msg::JiSearchFailed => "Failed to search memories: {}",
+        Msg::JiListFailed => "Failed to read memories: {}",
+        Msg::JiUpdateFailed => "Failed to update memory: {}",
+        Msg::JiDeleteFailed => "Failed to delete memory: {}",
+        Msg::JiMoveFailed => "Failed to move memory: {}",
+        Msg::JiPromoteFailed => "Failed to promote memory: {}",
+        Msg::JiHistoryFailed => "Failed to read the change history: {}",
+        Msg::JiUndoFailed => "Failed to undo change: {}",
+        Msg::JiOrganizeFailed => "Failed to organize memories: {}",
+        Msg::JiImportFailed => "Failed to import rule file: {}",
+        Msg::JiExportFailed => "Failed to export rule file: {}",
+        Msg::JiAdded => "✅ Memory added, id: {}\n📝 Content: {}\n📂 Category: {}",
+        Msg::JiAddedScope => "\n🗂️ Scope: {}",
+        Msg::JiAddedBranch => "\n🌿 Only visible on branch {}",
+        Msg::JiAddedConflicts => "\n⚠️ May contradict these existing memories; review them and use update or delete:",
+        Msg::JiDuplicate => "⏭️ A similar memory already exists ({}% similar), nothing was added\n🆔 Existing id: {}\n📝 Content: {}\nSet on_duplicate to merge to merge them",
+        Msg::JiMerged => "🔀 Merged into a similar memory ({}% similar), id: {}\n📝 Content: {}",
+        Msg::JiUpdated => "✅ Memory updated, id: {}\n📝 Content: {}\n📂 Category: {}",
+        Msg::JiDeleted => "🗑️ Memory deleted, id: {}\n📝 Content: {}",
+        Msg::JiMoved => "✅ Memory moved to {}, id: {}",
+        Msg::JiPromoted => "⬆️ Memory now applies to all branches, id: {}\n📝 Content: {}",
+        Msg::JiUndone => "↩️ Change {} undone, memory id: {}\n📝 Current state: {}\n🆔 Change id of this undo: {}",
+        Msg::JiUndoneDeleted => "deleted",
+        Msg::JiTags => "\n🏷️ Tags: {}",
+        Msg::JiExpiresAt => "\n⏳ Expires at: {}",
+        Msg::JiSecretsMasked => "\n🔒 Masked {} suspected secret(s) ({}); only the masked content is stored, keep secrets in environment variables or a secret manager",
+        Msg::JiImportLine => "- {}: imported {}, skipped {} existing",
+        Msg::JiImportDone => "📥 Rule files imported\n{}",
+        Msg::JiExportCreated => "- {}: created with {} memories",
+        Msg::JiExportUpdated => "- {}: updated with {} memories",
+        Msg::JiExportDone => "📤 Rule files exported\n{}",
+        Msg::JiOrganizeClean => "✨ Memories are already organized, nothing to change",
+        Msg::JiOrganizeWritten => "\n📄 Report written to: {}",
+        Msg::JiOrganizePreview => "\n👀 This is a preview, no files were changed. To apply it, call organize again with plan_id=\"{}\"",
+        Msg::JiSearchEmpty => "📭 No memories related to \"{}\"",
+        Msg::JiSearchFound => "🔍 Found {} memories related to \"{}\"",
+        Msg::JiSearchScore => "score {}",
+        Msg::JiHistoryEmpty => "📭 No changes recorded yet",
+        Msg::JiHistoryHeader => "🕘 Last {} changes (pass a change id to undo to revert one)",
+        Msg::JiHistoryUndoes => " ↩️ undoes {}",
+        Msg::JiListEmpty => "📭 No project memories yet",
+        Msg::JiListHeader => "📋 {} memories",
+        Msg::JiListBranch => " (branch {})",
+        Msg::JiListExpired => " (expired, removed on organize)",
+        Msg::JiListExpiresOn => " (expires {})",
+        Msg::JiRecallEmpty => "📭 No project memories related to \"{}\"",
+        Msg::JiBranchTag => "[branch {}] ",
+        Msg::JiTruncated => "✂️ {} more memories omitted due to length limits ({}). Use search to look up by keyword, list to see all, or raise max_chars/max_tokens",
+        Msg::JiTruncatedCount => "{}: {}",
+        Msg::JiTruncatedSeparator => ", ",
+        Msg::JiScopeProject => "project",
+        Msg::JiScopeTeam => "team",
+        Msg::JiScopeGlobal => "global",
+        Msg::JiSourceAgent => "agent",
+        Msg::JiSourceGui => "settings",
+        Msg::JiKindAdd => "add",
+        Msg::JiKindUpdate => "update",
+        Msg::JiKindDelete => "delete",
+        Msg::JiOrganizeReport => "# Memory organize report\n\n- Plan ID: {}\n- Status: {}\n- Duplicates merged: {}\n- Whitespace normalized: {}\n- Stale context archived: {}\n- Expired entries removed: {}\n",
+        Msg::JiOrganizeApplied => "applied",
+        Msg::JiOrganizeUnapplied => "preview (not written)",
+        Msg::JiOrganizeNothing => "\nNothing to organize\n",
+        Msg::JiCategoryRuleTitle => "Development rules and conventions",
+        Msg::JiCategoryRuleLabel => "Rules",
+        Msg::JiCategoryPreferenceTitle => "User preferences",
+        Msg::JiCategoryPreferenceLabel => "Preferences",
+        Msg::JiCategoryPatternTitle => "Common patterns and best practices",
+        Msg::JiCategoryPatternLabel => "Patterns",
+        Msg::JiCategoryContextTitle => "Project context",
+        Msg::JiCategoryContextLabel => "Context",
+        Msg::JiArchivedTitle => "{} (archived)",
+        Msg::JiLayoutCategory => "one file per category",
+        Msg::JiLayoutEntry => "one file per entry",
+        Msg::JiOverviewPrefix => "📚 Project memory overview: ",
+        Msg::JiOverviewEmpty => "📭 No valid project memories",
+        Msg::JiErrCreateRepoDir => "Cannot create the memory directory in the git project: {}\nError: {}\nThe project directory may not be writable; you can switch memory storage to the app data directory in settings.",
+        Msg::JiErrCreateAppDataDir => "Cannot create the memory directory in the app data directory: {}\nError: {}",
+        Msg::JiErrCreateScopeDir => "Cannot create the {} memory directory: {}\nError: {}",
+        Msg::JiErrPathFormat => "Invalid path format: {}",
+        Msg::JiErrPathMissing => "Project path does not exist: {}\nInput: {}\nNormalized: {}",
+        Msg::JiErrPathNotDir => "Project path is not a directory: {}",
+        Msg::JiErrNotGitRepo => "Error: the project path is not inside a git repository.\nPath: {}\nCall this from the git root (the directory containing .git).",
+        Msg::JiErrCategoryUndefined => "Category {} is not defined in {} memory. Available: {}",
+        Msg::JiErrMemoryNotFound => "No memory with ID {}",
+        Msg::JiErrPromoteNoBranch => "Memory {} is not limited to a branch, nothing to promote",
+        Msg::JiErrChangeNotFound => "No change with ID {}",
+        Msg::JiErrChangeAlreadyUndone => "Change {} was already undone by change {}",
+        Msg::JiErrChangeSuperseded => "Memory {} was modified after change {}; undo the later changes first",
+        Msg::JiErrLayoutUnchanged => "The memory directory already uses {}",
+        Msg::JiErrPlanStale => "The organize plan is stale because memory files changed after the preview. Preview again. Current plan ID: {}",
+        Msg::JiErrNoConfigDir => "Cannot determine the config directory",
+        Msg::JiErrTeamNotConfigured => "No team memory directory is configured; set the team memory path in settings first",
+        Msg::JiErrScopeUnavailable => "Memory scope unavailable: {}",
+        Msg::JiErrTtlConflict => "Provide either ttl or expires_at, not both",
+        Msg::JiErrTtlTooLong => "TTL is too long: {}",
+        Msg::JiErrExpiryPast => "Expiry time {} is already in the past",
+        Msg::JiErrTtlInvalid => "Invalid TTL: {}. Examples: 12h, 7d, 2w",
+        Msg::JiErrTtlUnit => "Invalid TTL unit: {}. Use m, h, d or w",
+        Msg::JiErrExpiresAtInvalid => "Invalid expiry time: {}. Use YYYY-MM-DD or RFC 3339",
+        Msg::JiErrSecretRejected => "The memory looks like it contains secrets ({}) and was rejected. Remove them and try again; keep secrets in environment variables or a secret manager",
+
+        Msg::TgButtonEnhance => "✨ Enhance",
+        Msg::TgButtonContinue => "⏩ Continue",
+        Msg::TgButtonSend => "↗️ Send",
+        Msg::TgOperationPrompt => "👇 Tap a button to finish",
+        Msg::TgSendSuccess => "✅ Sent!",
+        Msg::TgSelectedOptions => "📝 Selected options:",
+        Msg::TgNone => "None",
+        Msg::TgExtraInput => "📝 Additional notes:",
+        Msg::TgEnhanceSent => "✨ Enhancement request sent, waiting for the AI...",
+        Msg::TgEnhanceSentWithText => "✨ Enhancement request sent\n\n📝 Original: {}",
+        Msg::TgEnhanceResult => "✨ Enhanced draft (send as is, or reply with new text to replace it):\n\n{}",
+        Msg::TgEnhanceFailed => "❌ Enhancement failed: {}",
+        Msg::TgTestMessage => "🤖 Cunzhi test message\n\nThis is a test message from Cunzhi. Your Telegram bot is configured correctly!",
+        Msg::TgTestSuccess => "Test message sent! The Telegram bot is configured correctly.",
+
+        Msg::ErrProjectPath => "Invalid project path: {}",
+        Msg::ErrPopupCreation => "Failed to create popup: {}",
+        Msg::ErrResponseParsing => "Failed to parse response: {}",
+        Msg::ErrMemory => "Memory error: {}",
+        Msg::ErrIo => "IO error: {}",
+        Msg::ErrJson => "JSON serialization error: {}",
+        Msg::ErrGeneric => "Error: {}",
+    }
+}
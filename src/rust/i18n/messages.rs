@@ -0,0 +1,192 @@
+/// 服务端文案标识
+///
+/// 新增条目时需要在每个语言目录中补充翻译，match 穷尽检查保证不会遗漏
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // MCP 服务器与工具描述
+    ServerInstructions,
+    ZhiDescription,
+    ZhiParamMessage,
+    ZhiParamOptions,
+    ZhiParamMarkdown,
+    ZhiParamProjectPath,
+    JiDescription,
+    JiParamAction,
+    JiParamProjectPath,
+    JiParamContent,
+    JiParamCategory,
+    JiParamId,
+    JiParamQuery,
+    JiParamLimit,
+    JiParamOnDuplicate,
+    JiParamScope,
+    JiParamTags,
+    JiParamPlanId,
+    JiParamRulesFile,
+    JiParamMaxChars,
+    JiParamMaxTokens,
+    JiParamBranch,
+    JiParamTtl,
+    JiParamExpiresAt,
+    SouDescription,
+    SouParamProjectRoot,
+    SouParamQuery,
+    JiDisabled,
+    SouDisabled,
+    UnknownTool,
+    ParamParseFailed,
+
+    // 回复内容
+    UserCancelled,
+    NoContent,
+    SelectedOptions,
+    ImageTitle,
+    ImageFilename,
+    ImageType,
+    ImageSize,
+    ImagePreview,
+    ImageBase64Length,
+    ImageNote,
+
+    // 记忆工具回复
+    JiPathInvalid,
+    JiOpenFailed,
+    JiMissingContent,
+    JiMissingQuery,
+    JiMissingId,
+    JiMissingChangeId,
+    JiUpdateMissingFields,
+    JiMoveMissingCategory,
+    JiUnknownCategory,
+    JiUnknownRulesFile,
+    JiNoRulesFile,
+    JiNoRulesFileToExport,
+    JiUnknownBranch,
+    JiAddFailed,
+    JiRecallFailed,
+    JiSearchFailed,
+    JiListFailed,
+    JiUpdateFailed,
+    JiDeleteFailed,
+    JiMoveFailed,
+    JiPromoteFailed,
+    JiHistoryFailed,
+    JiUndoFailed,
+    JiOrganizeFailed,
+    JiImportFailed,
+    JiExportFailed,
+    JiAdded,
+    JiAddedScope,
+    JiAddedBranch,
+    JiAddedConflicts,
+    JiDuplicate,
+    JiMerged,
+    JiUpdated,
+    JiDeleted,
+    JiMoved,
+    JiPromoted,
+    JiUndone,
+    JiUndoneDeleted,
+    JiTags,
+    JiExpiresAt,
+    JiSecretsMasked,
+    JiImportLine,
+    JiImportDone,
+    JiExportCreated,
+    JiExportUpdated,
+    JiExportDone,
+    JiOrganizeClean,
+    JiOrganizeWritten,
+    JiOrganizePreview,
+    JiSearchEmpty,
+    JiSearchFound,
+    JiSearchScore,
+    JiHistoryEmpty,
+    JiHistoryHeader,
+    JiHistoryUndoes,
+    JiListEmpty,
+    JiListHeader,
+    JiListBranch,
+    JiListExpired,
+    JiListExpiresOn,
+    JiRecallEmpty,
+    JiBranchTag,
+    JiTruncated,
+    JiTruncatedCount,
+    JiTruncatedSeparator,
+    JiScopeProject,
+    JiScopeTeam,
+    JiScopeGlobal,
+    JiSourceAgent,
+    JiSourceGui,
+    JiKindAdd,
+    JiKindUpdate,
+    JiKindDelete,
+    JiOrganizeReport,
+    JiOrganizeApplied,
+    JiOrganizeUnapplied,
+    JiOrganizeNothing,
+    JiCategoryRuleTitle,
+    JiCategoryRuleLabel,
+    JiCategoryPreferenceTitle,
+    JiCategoryPreferenceLabel,
+    JiCategoryPatternTitle,
+    JiCategoryPatternLabel,
+    JiCategoryContextTitle,
+    JiCategoryContextLabel,
+    JiArchivedTitle,
+    JiLayoutCategory,
+    JiLayoutEntry,
+    JiOverviewPrefix,
+    JiOverviewEmpty,
+    JiErrCreateRepoDir,
+    JiErrCreateAppDataDir,
+    JiErrCreateScopeDir,
+    JiErrPathFormat,
+    JiErrPathMissing,
+    JiErrPathNotDir,
+    JiErrNotGitRepo,
+    JiErrCategoryUndefined,
+    JiErrMemoryNotFound,
+    JiErrPromoteNoBranch,
+    JiErrChangeNotFound,
+    JiErrChangeAlreadyUndone,
+    JiErrChangeSuperseded,
+    JiErrLayoutUnchanged,
+    JiErrPlanStale,
+    JiErrNoConfigDir,
+    JiErrTeamNotConfigured,
+    JiErrScopeUnavailable,
+    JiErrTtlConflict,
+    JiErrTtlTooLong,
+    JiErrExpiryPast,
+    JiErrTtlInvalid,
+    JiErrTtlUnit,
+    JiErrExpiresAtInvalid,
+    JiErrSecretRejected,
+
+    // Telegram
+    TgButtonEnhance,
+    TgButtonContinue,
+    TgButtonSend,
+    TgOperationPrompt,
+    TgSendSuccess,
+    TgSelectedOptions,
+    TgNone,
+    TgExtraInput,
+    TgEnhanceSent,
+    TgEnhanceSentWithText,
+    TgEnhanceResult,
+    TgEnhanceFailed,
+    TgTestMessage,
+    TgTestSuccess,
+
+    // 错误信息
+    ErrProjectPath,
+    ErrPopupCreation,
+    ErrResponseParsing,
+    ErrMemory,
+    ErrIo,
+    ErrJson,
+    ErrGeneric,
+}
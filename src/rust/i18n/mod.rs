@@ -0,0 +1,129 @@
+//! 服务端文案国际化
+//!
+//! 工具描述、回复内容、Telegram 按钮和错误信息统一从消息目录获取，
+//! 语言由 `AppConfig.locale` 决定（目前支持 zh-CN 和 en）
+
+pub mod en;
+pub mod messages;
+pub mod zh_cn;
+
+pub use messages::Msg;
+
+use std::fmt::Display;
+use std::sync::RwLock;
+
+use crate::config::load_standalone_config;
+
+static CURRENT_LOCALE: RwLock<Option<Locale>> = RwLock::new(None);
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    ZhCn,
+    En,
+}
+
+impl Locale {
+    /// 解析语言代码，未知代码回退到中文
+    pub fn from_code(code: &str) -> Self {
+        match code.trim().to_lowercase().as_str() {
+            "en" | "en-us" | "en-gb" | "en_us" => Self::En,
+            _ => Self::ZhCn,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ZhCn => "zh-CN",
+            Self::En => "en",
+        }
+    }
+
+    /// 检查语言代码是否受支持
+    pub fn is_supported(code: &str) -> bool {
+        SUPPORTED_LOCALES.contains(&code)
+    }
+}
+
+/// 支持的语言代码列表
+pub const SUPPORTED_LOCALES: &[&str] = &["zh-CN", "en"];
+
+/// 获取当前语言，首次调用时从配置文件读取
+pub fn current_locale() -> Locale {
+    if let Ok(guard) = CURRENT_LOCALE.read() {
+        if let Some(locale) = *guard {
+            return locale;
+        }
+    }
+    reload_locale()
+}
+
+/// 重新从配置文件读取语言设置
+pub fn reload_locale() -> Locale {
+    let locale = load_standalone_config()
+        .map(|config| Locale::from_code(&config.locale))
+        .unwrap_or(Locale::ZhCn);
+    set_locale(locale);
+    locale
+}
+
+/// 设置当前语言（GUI修改设置后调用）
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut guard) = CURRENT_LOCALE.write() {
+        *guard = Some(locale);
+    }
+}
+
+/// 按当前语言获取文案
+pub fn t(msg: Msg) -> &'static str {
+    t_in(current_locale(), msg)
+}
+
+/// 按指定语言获取文案
+pub fn t_in(locale: Locale, msg: Msg) -> &'static str {
+    match locale {
+        Locale::ZhCn => zh_cn::get(msg),
+        Locale::En => en::get(msg),
+    }
+}
+
+/// 获取文案并按顺序替换其中的 `{}` 占位符
+pub fn tf(msg: Msg, args: &[&dyn Display]) -> String {
+    format_message(t(msg), args)
+}
+
+fn format_message(template: &str, args: &[&dyn Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find("{}") {
+        result.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => result.push_str(&arg.to_string()),
+            None => result.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_message_in_both_locales() {
+        let zh = format_message(t_in(Locale::ZhCn, Msg::ImageTitle), &[&2]);
+        let en = format_message(t_in(Locale::En, Msg::ImageTitle), &[&2]);
+        assert_eq!(zh, "=== 图片 2 ===");
+        assert_eq!(en, "=== Image 2 ===");
+
+        // 参数不足时保留占位符，多余参数忽略
+        assert_eq!(format_message("{} / {}", &[&1]), "1 / {}");
+        assert_eq!(format_message("无占位符", &[&1]), "无占位符");
+        assert_eq!(Locale::from_code("en-US"), Locale::En);
+    }
+}
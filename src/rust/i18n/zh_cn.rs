@@ -0,0 +1,188 @@
+use super::Msg;
+
+/// 简体中文文案
+pub fn get(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ServerInstructions => "Zhi 智能代码审查工具，支持交互式对话和记忆管理",
+        Msg::ZhiDescription => "智能代码审查交互工具，支持预定义选项、自由文本输入和图片上传",
+        Msg::ZhiParamMessage => "要显示给用户的消息",
+        Msg::ZhiParamOptions => "预定义的选项列表（可选）",
+        Msg::ZhiParamMarkdown => "消息是否为Markdown格式，默认为true",
+        Msg::ZhiParamProjectPath => "当前项目路径（可选），用于匹配自动回复策略",
+        Msg::JiDescription => "全局记忆管理工具，用于存储和管理重要的开发规范、用户偏好和最佳实践",
+        Msg::JiParamAction => "操作类型：记忆/add(添加记忆), 回忆/recall(获取项目信息), 列表/list(列出记忆及ID), 更新/update(按ID更新), 删除/delete(按ID删除), 移动/move(按ID移动到其他分类), 搜索/search(按相关度检索记忆), 整理/organize(删除已过期记忆、合并重复、规范空白并归档长期未更新的上下文，先预览后执行), 导入/import(从其他智能体规则文件导入记忆), 导出/export(将记忆写入其他智能体规则文件的托管区块), 提升/promote(按ID将分支记忆提升为对所有分支生效), 历史/history(查看添加、更新、删除的变更记录), 撤销/undo(按变更ID撤销一次变更)",
+        Msg::JiParamProjectPath => "项目路径（必需）",
+        Msg::JiParamContent => "记忆内容（记忆、更新操作时必需）",
+        Msg::JiParamCategory => "记忆分类：内置 rule(规范规则), preference(用户偏好), pattern(最佳实践), context(项目上下文)，也可使用配置或项目 categories.json 中声明的自定义分类。添加时默认为context，移动时为目标分类，列表时用于筛选",
+        Msg::JiParamId => "记忆ID（更新、删除、移动、提升操作时必需，可通过列表操作获取；历史操作时可选，只看该记忆的变更；撤销操作时填写历史操作返回的变更ID）",
+        Msg::JiParamQuery => "检索关键词（搜索操作时必需；回忆操作时可选，用于只返回相关记忆）",
+        Msg::JiParamLimit => "返回结果数量上限（搜索、回忆操作时可选，默认10；历史操作时可选，默认20）",
+        Msg::JiParamOnDuplicate => "添加时遇到相似记忆的处理方式：reject(拒绝并返回已有ID，默认), merge(与已有记忆合并), allow(仍然添加)",
+        Msg::JiParamScope => "记忆作用域：project(当前项目), team(团队共享目录), global(用户全局)。写入时默认为project；读取时不指定则按 project > team > global 合并",
+        Msg::JiParamTags => "标签：添加时为记忆打标签，更新时替换原有标签（传空数组清空），列表、搜索、回忆时只返回包含全部标签的记忆",
+        Msg::JiParamPlanId => "整理计划ID：整理操作不带该参数时只预览差异，确认后传入预览返回的计划ID才会改写文件",
+        Msg::JiParamRulesFile => "规则文件：claude(CLAUDE.md), agents(AGENTS.md), cursor(.cursorrules), copilot(.github/copilot-instructions.md), windsurf(.windsurfrules)。导入、导出操作时可选，未指定时处理项目中已存在的全部规则文件",
+        Msg::JiParamMaxChars => "回忆输出的最大字符数（回忆操作时可选），0 表示不限制",
+        Msg::JiParamMaxTokens => "回忆输出的最大 token 数（回忆操作时可选），未提供 max_chars 和 max_tokens 时使用配置中的默认值，0 表示不限制",
+        Msg::JiParamTtl => "记忆有效期（添加、更新时可选），如 12h、7d、2w，never 表示永不过期。未提供时使用该分类配置的默认有效期。过期的记忆不再被回忆和搜索到，整理时删除",
+        Msg::JiParamExpiresAt => "记忆过期时间（添加、更新时可选），YYYY-MM-DD 或 RFC 3339 格式，与 ttl 二选一",
+        Msg::JiParamBranch => "限定记忆生效的 git 分支（添加时可选），填写 current 表示当前分支。分支记忆只在该分支检出时被回忆和搜索到",
+        Msg::SouDescription => "基于查询在特定项目中搜索相关的代码上下文。此工具在搜索前自动执行增量索引，确保结果始终是最新的。返回代码库中与查询语义相关的格式化文本片段。",
+        Msg::SouParamProjectRoot => "项目根目录的绝对路径，使用正斜杠(/)作为分隔符。例如：C:/Users/username/projects/myproject",
+        Msg::SouParamQuery => "用于查找相关代码上下文的自然语言搜索查询。此工具执行语义搜索并返回与查询匹配的代码片段。例如：'日志配置设置初始化logger'（查找日志设置代码）、'用户认证登录'（查找认证相关代码）、'数据库连接池'（查找数据库连接代码）、'错误处理异常'（查找错误处理模式）、'API端点路由'（查找API路由定义）。工具返回带有文件路径和行号的格式化文本片段，显示相关代码的位置。",
+        Msg::JiDisabled => "记忆管理工具已被禁用",
+        Msg::SouDisabled => "代码搜索工具已被禁用",
+        Msg::UnknownTool => "未知的工具: {}",
+        Msg::ParamParseFailed => "参数解析失败: {}",
+
+        Msg::UserCancelled => "用户取消了操作",
+        Msg::NoContent => "用户未提供任何内容",
+        Msg::SelectedOptions => "选择的选项: {}",
+        Msg::ImageTitle => "=== 图片 {} ===",
+        Msg::ImageFilename => "文件名: {}",
+        Msg::ImageType => "类型: {}",
+        Msg::ImageSize => "大小: {}",
+        Msg::ImagePreview => "Base64 预览: {}",
+        Msg::ImageBase64Length => "完整 Base64 长度: {} 字符",
+        Msg::ImageNote => "💡 注意：用户提供了 {} 张图片。如果 AI 助手无法显示图片，图片数据已包含在上述 Base64 信息中。",
+
+        Msg::JiPathInvalid => "路径验证失败: {}\n原始路径: {}\n请检查路径格式是否正确，特别是 Windows 路径应使用正确的盘符格式（如 C:\\path）",
+        Msg::JiOpenFailed => "创建记忆管理器失败: {}",
+        Msg::JiMissingContent => "缺少记忆内容",
+        Msg::JiMissingQuery => "缺少检索关键词",
+        Msg::JiMissingId => "缺少记忆ID，可先通过列表操作获取",
+        Msg::JiMissingChangeId => "缺少变更ID，可先通过历史操作获取",
+        Msg::JiUpdateMissingFields => "更新操作需要提供新的记忆内容、分类、标签或有效期",
+        Msg::JiMoveMissingCategory => "移动操作需要提供目标分类",
+        Msg::JiUnknownCategory => "未知的记忆分类: {}，可选值: {}",
+        Msg::JiUnknownRulesFile => "未知的规则文件: {}，可选值: {}",
+        Msg::JiNoRulesFile => "项目中没有找到规则文件",
+        Msg::JiNoRulesFileToExport => "项目中没有找到规则文件，请通过 rules_file 指定要创建的文件",
+        Msg::JiUnknownBranch => "无法确定当前分支，项目可能处于分离头指针状态，请直接填写分支名",
+        Msg::JiAddFailed => "添加记忆失败: {}",
+        Msg::JiRecallFailed => "获取项目信息失败: {}",
+        Msg::JiSearchFailed => "检索记忆失败: {}",
+        Msg::JiListFailed => "读取记忆失败: {}",
+        Msg::JiUpdateFailed => "更新记忆失败: {}",
+        Msg::JiDeleteFailed => "删除记忆失败: {}",
+        Msg::JiMoveFailed => "移动记忆失败: {}",
+        Msg::JiPromoteFailed => "提升记忆失败: {}",
+        Msg::JiHistoryFailed => "读取变更历史失败: {}",
+        Msg::JiUndoFailed => "撤销变更失败: {}",
+        Msg::JiOrganizeFailed => "整理记忆失败: {}",
+        Msg::JiImportFailed => "导入规则文件失败: {}",
+        Msg::JiExportFailed => "导出规则文件失败: {}",
+        Msg::JiAdded => "✅ 记忆已添加，ID: {}\n📝 内容: {}\n📂 分类: {}",
+        Msg::JiAddedScope => "\n🗂️ 作用域: {}",
+        Msg::JiAddedBranch => "\n🌿 仅在分支 {} 上可见",
+        Msg::JiAddedConflicts => "\n⚠️ 可能与以下已有记忆矛盾，请确认后通过 update 或 delete 处理：",
+        Msg::JiDuplicate => "⏭️ 已存在相似记忆（相似度 {}%），未重复添加\n🆔 已有ID: {}\n📝 内容: {}\n如需合并请设置 on_duplicate 为 merge",
+        Msg::JiMerged => "🔀 已与相似记忆合并（相似度 {}%），ID: {}\n📝 内容: {}",
+        Msg::JiUpdated => "✅ 记忆已更新，ID: {}\n📝 内容: {}\n📂 分类: {}",
+        Msg::JiDeleted => "🗑️ 记忆已删除，ID: {}\n📝 内容: {}",
+        Msg::JiMoved => "✅ 记忆已移动到 {}，ID: {}",
+        Msg::JiPromoted => "⬆️ 记忆已提升为对所有分支生效，ID: {}\n📝 内容: {}",
+        Msg::JiUndone => "↩️ 已撤销变更 {}，记忆ID: {}\n📝 当前状态: {}\n🆔 本次撤销的变更ID: {}",
+        Msg::JiUndoneDeleted => "已删除",
+        Msg::JiTags => "\n🏷️ 标签: {}",
+        Msg::JiExpiresAt => "\n⏳ 过期时间: {}",
+        Msg::JiSecretsMasked => "\n🔒 已遮盖 {} 处疑似密钥（{}），记忆中只保存遮盖后的内容，密钥请放在环境变量或密钥管理工具中",
+        Msg::JiImportLine => "- {}: 导入 {} 条，跳过 {} 条已有记忆",
+        Msg::JiImportDone => "📥 规则文件导入完成\n{}",
+        Msg::JiExportCreated => "- {}: 已创建，写入 {} 条记忆",
+        Msg::JiExportUpdated => "- {}: 已更新，写入 {} 条记忆",
+        Msg::JiExportDone => "📤 规则文件导出完成\n{}",
+        Msg::JiOrganizeClean => "✨ 记忆已是整理后的状态，无需改动",
+        Msg::JiOrganizeWritten => "\n📄 报告已写入: {}",
+        Msg::JiOrganizePreview => "\n👀 以上为预览，文件尚未改动。确认后请以 plan_id=\"{}\" 再次调用整理操作",
+        Msg::JiSearchEmpty => "📭 没有与「{}」相关的记忆",
+        Msg::JiSearchFound => "🔍 找到 {} 条与「{}」相关的记忆",
+        Msg::JiSearchScore => "相关度 {}",
+        Msg::JiHistoryEmpty => "📭 暂无变更记录",
+        Msg::JiHistoryHeader => "🕘 最近 {} 条变更（可用 undo 并传入变更ID撤销）",
+        Msg::JiHistoryUndoes => " ↩️ 撤销 {}",
+        Msg::JiListEmpty => "📭 暂无项目记忆",
+        Msg::JiListHeader => "📋 共 {} 条记忆",
+        Msg::JiListBranch => " (分支 {})",
+        Msg::JiListExpired => " (已过期，整理时删除)",
+        Msg::JiListExpiresOn => " ({} 过期)",
+        Msg::JiRecallEmpty => "📭 没有与「{}」相关的项目记忆",
+        Msg::JiBranchTag => "[分支 {}] ",
+        Msg::JiTruncated => "✂️ 另有 {} 条记忆因长度限制未显示（{}），可用 search 按关键词检索、list 查看全部，或提高 max_chars/max_tokens",
+        Msg::JiTruncatedCount => "{} {} 条",
+        Msg::JiTruncatedSeparator => "，",
+        Msg::JiScopeProject => "项目",
+        Msg::JiScopeTeam => "团队",
+        Msg::JiScopeGlobal => "全局",
+        Msg::JiSourceAgent => "智能体",
+        Msg::JiSourceGui => "设置界面",
+        Msg::JiKindAdd => "添加",
+        Msg::JiKindUpdate => "更新",
+        Msg::JiKindDelete => "删除",
+        Msg::JiOrganizeReport => "# 记忆整理报告\n\n- 计划ID: {}\n- 状态: {}\n- 合并重复: {}\n- 规范空白: {}\n- 归档长期未更新的上下文: {}\n- 删除已过期条目: {}\n",
+        Msg::JiOrganizeApplied => "已执行",
+        Msg::JiOrganizeUnapplied => "预览（未写入）",
+        Msg::JiOrganizeNothing => "\n没有需要整理的内容\n",
+        Msg::JiCategoryRuleTitle => "开发规范和规则",
+        Msg::JiCategoryRuleLabel => "规范",
+        Msg::JiCategoryPreferenceTitle => "用户偏好设置",
+        Msg::JiCategoryPreferenceLabel => "偏好",
+        Msg::JiCategoryPatternTitle => "常用模式和最佳实践",
+        Msg::JiCategoryPatternLabel => "模式",
+        Msg::JiCategoryContextTitle => "项目上下文信息",
+        Msg::JiCategoryContextLabel => "背景",
+        Msg::JiArchivedTitle => "{}（已归档）",
+        Msg::JiLayoutCategory => "按分类存放",
+        Msg::JiLayoutEntry => "按条目存放",
+        Msg::JiOverviewPrefix => "📚 项目记忆总览: ",
+        Msg::JiOverviewEmpty => "📭 暂无有效项目记忆",
+        Msg::JiErrCreateRepoDir => "无法在git项目中创建记忆目录: {}\n错误: {}\n这可能是因为项目目录没有写入权限，可在设置中将记忆存储位置改为应用数据目录。",
+        Msg::JiErrCreateAppDataDir => "无法在应用数据目录中创建记忆目录: {}\n错误: {}",
+        Msg::JiErrCreateScopeDir => "无法创建{}记忆目录: {}\n错误: {}",
+        Msg::JiErrPathFormat => "路径格式错误: {}",
+        Msg::JiErrPathMissing => "项目路径不存在: {}\n原始输入: {}\n规范化后: {}",
+        Msg::JiErrPathNotDir => "项目路径不是目录: {}",
+        Msg::JiErrNotGitRepo => "错误：提供的项目路径不在 git 仓库中。\n路径: {}\n请确保在 git 根目录（包含 .git 文件夹的目录）中调用此功能。",
+        Msg::JiErrCategoryUndefined => "分类 {} 未在{}记忆中定义，可选值: {}",
+        Msg::JiErrMemoryNotFound => "未找到ID为 {} 的记忆",
+        Msg::JiErrPromoteNoBranch => "记忆 {} 未限定分支，无需提升",
+        Msg::JiErrChangeNotFound => "未找到ID为 {} 的变更记录",
+        Msg::JiErrChangeAlreadyUndone => "变更 {} 已被变更 {} 撤销",
+        Msg::JiErrChangeSuperseded => "记忆 {} 在变更 {} 之后又被修改过，请先撤销之后的变更",
+        Msg::JiErrLayoutUnchanged => "记忆目录已经是{}",
+        Msg::JiErrPlanStale => "整理计划已失效，记忆文件在预览后发生了变化，请重新预览。当前计划ID: {}",
+        Msg::JiErrNoConfigDir => "无法获取配置目录",
+        Msg::JiErrTeamNotConfigured => "未配置团队记忆目录，请先在设置中填写团队记忆路径",
+        Msg::JiErrScopeUnavailable => "记忆作用域不可用: {}",
+        Msg::JiErrTtlConflict => "ttl 和 expires_at 只能提供一个",
+        Msg::JiErrTtlTooLong => "有效期过长: {}",
+        Msg::JiErrExpiryPast => "过期时间 {} 已经过去",
+        Msg::JiErrTtlInvalid => "无效的有效期: {}，示例: 12h、7d、2w",
+        Msg::JiErrTtlUnit => "无效的有效期单位: {}，可选 m、h、d、w",
+        Msg::JiErrExpiresAtInvalid => "无效的过期时间: {}，请使用 YYYY-MM-DD 或 RFC 3339 格式",
+        Msg::JiErrSecretRejected => "记忆内容包含疑似密钥（{}），已拒绝写入。请去掉密钥后重试，密钥应保存在环境变量或密钥管理工具中",
+
+        Msg::TgButtonEnhance => "✨ 增强",
+        Msg::TgButtonContinue => "⏩ 继续",
+        Msg::TgButtonSend => "↗️ 发送",
+        Msg::TgOperationPrompt => "👇 点击按钮完成操作",
+        Msg::TgSendSuccess => "✅ 发送成功！",
+        Msg::TgSelectedOptions => "📝 选中的选项：",
+        Msg::TgNone => "无",
+        Msg::TgExtraInput => "📝 补充说明：",
+        Msg::TgEnhanceSent => "✨ 增强请求已发送，等待 AI 处理...",
+        Msg::TgEnhanceSentWithText => "✨ 增强请求已发送\n\n📝 原文：{}",
+        Msg::TgEnhanceResult => "✨ 增强结果（可直接发送，或回复新文本替换）：\n\n{}",
+        Msg::TgEnhanceFailed => "❌ 增强失败：{}",
+        Msg::TgTestMessage => "🤖 寸止应用测试消息\n\n这是一条来自寸止应用的测试消息，表示Telegram Bot配置成功！",
+        Msg::TgTestSuccess => "测试消息发送成功！Telegram Bot配置正确。",
+
+        Msg::ErrProjectPath => "项目路径错误: {}",
+        Msg::ErrPopupCreation => "弹窗创建失败: {}",
+        Msg::ErrResponseParsing => "响应解析失败: {}",
+        Msg::ErrMemory => "记忆管理错误: {}",
+        Msg::ErrIo => "IO 错误: {}",
+        Msg::ErrJson => "JSON 序列化错误: {}",
+        Msg::ErrGeneric => "通用错误: {}",
+    }
+}
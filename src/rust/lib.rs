@@ -1,6 +1,8 @@
 pub mod app;
 pub mod config;
 pub mod constants;
+pub mod daemon;
+pub mod i18n;
 pub mod mcp;
 pub mod telegram;
 pub mod ui;
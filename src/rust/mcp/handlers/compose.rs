@@ -0,0 +1,146 @@
+use crate::config::{load_standalone_config, CustomPromptConfig};
+use crate::{log_debug, log_important};
+
+/// 增强请求的回复来源，回复内容是发给AI的增强prompt而不是用户输入，不追加上下文
+const ENHANCE_SOURCES: &[&str] = &["popup_enhance", "telegram_enhance"];
+
+/// 把启用的上下文追加内容合成到回复中
+///
+/// 在寸止进程中统一处理，无论是弹窗、Telegram还是自动回复策略给出的回复，
+/// 追加的内容都一致。非结构化回复（如取消）和增强请求保持原样
+pub fn apply_prompt_composition(response: &str) -> String {
+    let config = match load_standalone_config() {
+        Ok(config) => config,
+        Err(e) => {
+            log_important!(warn, "加载配置失败，跳过上下文追加: {}", e);
+            return response.to_string();
+        }
+    };
+
+    match compose_prompt_suffix(&config.custom_prompt_config) {
+        Some(suffix) => append_to_response(response, &suffix),
+        None => response.to_string(),
+    }
+}
+
+/// 按 sort_order 生成需要追加的内容
+///
+/// 条件性prompt按当前开关状态取对应模板，普通prompt仅在开启 `append_to_response` 时追加
+pub fn compose_prompt_suffix(config: &CustomPromptConfig) -> Option<String> {
+    if !config.enabled || !config.compose_responses {
+        return None;
+    }
+
+    let mut prompts: Vec<_> = config.prompts.iter().collect();
+    prompts.sort_by_key(|p| p.sort_order);
+
+    let parts: Vec<&str> = prompts
+        .into_iter()
+        .filter_map(|prompt| {
+            let text = if prompt.r#type == "conditional" {
+                if prompt.current_state {
+                    prompt.template_true.as_deref()
+                } else {
+                    prompt.template_false.as_deref()
+                }
+            } else if prompt.append_to_response {
+                Some(prompt.content.as_str())
+            } else {
+                None
+            };
+            text.map(str::trim).filter(|t| !t.is_empty())
+        })
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("\n"))
+    }
+}
+
+/// 把追加内容写入结构化回复的 user_input
+fn append_to_response(response: &str, suffix: &str) -> String {
+    let mut value: serde_json::Value = match serde_json::from_str(response) {
+        Ok(value @ serde_json::Value::Object(_)) => value,
+        _ => return response.to_string(),
+    };
+    let source = value.pointer("/metadata/source").and_then(|v| v.as_str()).unwrap_or("");
+    if ENHANCE_SOURCES.contains(&source) {
+        log_debug!("增强请求不追加上下文内容: {}", source);
+        return response.to_string();
+    }
+
+    let user_input = value
+        .get("user_input")
+        .and_then(|v| v.as_str())
+        .map(str::trim_end)
+        .unwrap_or("");
+    let composed = if user_input.is_empty() {
+        suffix.to_string()
+    } else {
+        format!("{}\n\n{}", user_input, suffix)
+    };
+
+    log_debug!("追加上下文内容: {} 字符", suffix.chars().count());
+    value["user_input"] = serde_json::Value::String(composed);
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{default_custom_prompt_config, CustomPrompt};
+    use crate::mcp::types::build_send_response;
+
+    fn prompt(id: &str, sort_order: i32, r#type: &str) -> CustomPrompt {
+        CustomPrompt {
+            id: id.to_string(),
+            name: id.to_string(),
+            content: String::new(),
+            description: None,
+            sort_order,
+            created_at: String::new(),
+            updated_at: String::new(),
+            r#type: r#type.to_string(),
+            condition_text: None,
+            template_true: None,
+            template_false: None,
+            current_state: false,
+            append_to_response: false,
+        }
+    }
+
+    #[test]
+    fn test_compose_in_sort_order() {
+        let mut config = default_custom_prompt_config();
+
+        let mut conditional = prompt("c", 2, "conditional");
+        conditional.template_true = Some("✔️使用TypeScript".to_string());
+        conditional.template_false = Some("❌不要使用TypeScript".to_string());
+        conditional.current_state = true;
+
+        let mut normal = prompt("n", 1, "normal");
+        normal.content = "保持提交粒度小".to_string();
+        normal.append_to_response = true;
+
+        config.prompts = vec![conditional, normal, prompt("skip", 0, "normal")];
+
+        let suffix = compose_prompt_suffix(&config).unwrap();
+        assert_eq!(suffix, "保持提交粒度小\n✔️使用TypeScript");
+
+        let response = build_send_response(Some("好的".to_string()), vec![], vec![], None, "popup");
+        let composed: serde_json::Value =
+            serde_json::from_str(&append_to_response(&response, &suffix)).unwrap();
+        assert_eq!(composed["user_input"], "好的\n\n保持提交粒度小\n✔️使用TypeScript");
+
+        assert_eq!(append_to_response("CANCELLED", &suffix), "CANCELLED");
+        for source in ENHANCE_SOURCES {
+            let enhance = build_send_response(Some("请改写这段需求".to_string()), vec![], vec![], None, source);
+            assert_eq!(append_to_response(&enhance, &suffix), enhance);
+        }
+
+        config.compose_responses = false;
+        assert!(compose_prompt_suffix(&config).is_none());
+    }
+}
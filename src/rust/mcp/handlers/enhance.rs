@@ -0,0 +1,264 @@
+use anyhow::Result;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use std::time::Duration;
+
+use crate::config::{default_enhance_config, default_recall_max_tokens, load_standalone_config, EnhanceConfig};
+use crate::constants::enhance;
+use crate::mcp::tools::acemcp::mcp::retry_request;
+use crate::mcp::tools::memory::{LayeredMemory, MemoryFilter, RecallBudget};
+use crate::{log_debug, log_important};
+
+/// 渲染增强模板所需的上下文
+#[derive(Debug, Clone, Default)]
+pub struct EnhanceContext<'a> {
+    pub original_text: &'a str,
+    pub selected_options: &'a [String],
+    pub project_path: Option<&'a str>,
+}
+
+/// 独立加载增强配置（供寸止进程和纯Telegram模式使用），失败时使用默认配置
+pub fn load_enhance_config() -> EnhanceConfig {
+    match load_standalone_config() {
+        Ok(config) => config.enhance_config,
+        Err(e) => {
+            log_important!(warn, "加载配置失败，使用默认增强配置: {}", e);
+            default_enhance_config()
+        }
+    }
+}
+
+/// 按当前选中的模板渲染增强提示词
+///
+/// 找不到选中的模板时回退到内置英文模板，保证增强按钮始终可用
+pub fn render_enhance_prompt(config: &EnhanceConfig, context: &EnhanceContext) -> String {
+    let template = config
+        .templates
+        .iter()
+        .find(|t| t.id == config.active_template)
+        .map(|t| t.content.as_str())
+        .unwrap_or_else(|| {
+            log_important!(warn, "未找到增强模板 {}，使用默认模板", config.active_template);
+            enhance::DEFAULT_TEMPLATE_EN
+        });
+
+    render_template(template, context)
+}
+
+/// 替换模板变量
+///
+/// 只扫描一遍模板，替换进来的文本不会再被展开，用户输入中出现的 `{{memories}}` 等原样保留
+pub fn render_template(template: &str, context: &EnhanceContext) -> String {
+    const VARIABLES: [&str; 4] = [
+        enhance::VAR_ORIGINAL_TEXT,
+        enhance::VAR_SELECTED_OPTIONS,
+        enhance::VAR_PROJECT_PATH,
+        enhance::VAR_MEMORIES,
+    ];
+
+    // 记忆需要读取磁盘，只在模板用到时加载一次
+    let mut memories: Option<String> = None;
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(variable) = VARIABLES.into_iter().find(|variable| rest.starts_with(variable)) else {
+            rendered.push_str("{{");
+            rest = &rest[2..];
+            continue;
+        };
+        match variable {
+            enhance::VAR_ORIGINAL_TEXT => rendered.push_str(context.original_text.trim()),
+            enhance::VAR_SELECTED_OPTIONS => rendered.push_str(&context.selected_options.join(", ")),
+            enhance::VAR_PROJECT_PATH => rendered.push_str(context.project_path.unwrap_or("")),
+            _ => rendered.push_str(memories.get_or_insert_with(|| load_memories(context.project_path))),
+        }
+        rest = &rest[variable.len()..];
+    }
+    rendered.push_str(rest);
+
+    rendered
+}
+
+/// 只读地读取项目记忆摘要，没有项目路径或读取失败时返回空字符串
+fn load_memories(project_path: Option<&str>) -> String {
+    let Some(project_path) = project_path else {
+        return String::new();
+    };
+
+    let budget = load_standalone_config()
+        .map(|config| RecallBudget::resolve(None, None, config.memory_config.recall_max_tokens))
+        .unwrap_or_else(|_| RecallBudget::resolve(None, None, default_recall_max_tokens()));
+
+    match LayeredMemory::open_existing(project_path).and_then(|memory| memory.get_project_info(&MemoryFilter::default(), budget)) {
+        Ok(info) => info,
+        Err(e) => {
+            log_important!(warn, "读取项目记忆失败: {}", e);
+            String::new()
+        }
+    }
+}
+
+/// 是否使用模型接口直接改写
+pub fn is_llm_mode(config: &EnhanceConfig) -> bool {
+    config.mode == enhance::MODE_LLM
+}
+
+/// 调用OpenAI兼容的chat-completions接口改写用户草稿
+///
+/// 使用专用的改写模板而不是当前选中的增强模板，返回改写后的文本供用户确认或修改，不会直接发送给AI
+pub async fn enhance_with_llm(config: &EnhanceConfig, context: &EnhanceContext<'_>) -> Result<String> {
+    let llm = &config.llm;
+    if llm.base_url.trim().is_empty() || llm.model.trim().is_empty() {
+        anyhow::bail!("未配置增强模型接口地址或模型名称");
+    }
+
+    let prompt = render_template(enhance::LLM_TEMPLATE, context);
+    let url = format!("{}/chat/completions", llm.base_url.trim().trim_end_matches('/'));
+    let payload = serde_json::json!({
+        "model": llm.model,
+        "messages": [
+            { "role": "system", "content": enhance::LLM_SYSTEM_PROMPT },
+            { "role": "user", "content": prompt },
+        ],
+        "stream": false,
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(llm.timeout_secs))
+        .build()?;
+
+    log_debug!("调用增强模型: {} ({})", url, llm.model);
+
+    let value: serde_json::Value = retry_request(|| async {
+        let mut builder = client
+            .post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&payload);
+        if !llm.api_key.trim().is_empty() {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {}", llm.api_key.trim()));
+        }
+
+        let r = builder.send().await?;
+        let status = r.status();
+        if !status.is_success() {
+            let body = r.text().await.unwrap_or_default();
+            anyhow::bail!("HTTP {} {}", status, body);
+        }
+
+        Ok(r.json().await?)
+    }, enhance::LLM_MAX_RETRIES, 1.0).await?;
+
+    let content = value["choices"][0]["message"]["content"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("模型响应缺少内容"))?;
+
+    let enhanced = extract_enhanced_text(content);
+    if enhanced.is_empty() {
+        anyhow::bail!("模型返回了空的增强结果");
+    }
+
+    Ok(enhanced)
+}
+
+/// 提取 <augment-enhanced-prompt> 标签中的内容，没有标签时使用整段回复
+fn extract_enhanced_text(content: &str) -> String {
+    let tagged = content
+        .split_once(enhance::ENHANCED_TAG_START)
+        .and_then(|(_, rest)| rest.split_once(enhance::ENHANCED_TAG_END))
+        .map(|(inner, _)| inner);
+
+    tagged.unwrap_or(content).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// 启动只响应一次的本地 chat-completions 桩服务，返回基础地址和收到的请求体
+    fn spawn_stub_server(reply: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let response_body = serde_json::json!({
+                "choices": [{ "message": { "role": "assistant", "content": reply } }]
+            })
+            .to_string();
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response_body.len(),
+                response_body
+            )
+            .unwrap();
+
+            String::from_utf8(body).unwrap()
+        });
+
+        (base_url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_enhance_with_llm_against_stub_server() {
+        let (base_url, server) =
+            spawn_stub_server("好的\n<augment-enhanced-prompt>为登录接口补充单元测试</augment-enhanced-prompt>");
+
+        let mut config = default_enhance_config();
+        config.mode = enhance::MODE_LLM.to_string();
+        config.llm.base_url = base_url;
+        config.llm.model = "local-model".to_string();
+
+        let context = EnhanceContext {
+            original_text: "加测试",
+            selected_options: &[],
+            project_path: None,
+        };
+        let enhanced = enhance_with_llm(&config, &context).await.unwrap();
+        assert_eq!(enhanced, "为登录接口补充单元测试");
+
+        let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(request["model"], "local-model");
+        let prompt = request["messages"][1]["content"].as_str().unwrap();
+        assert!(prompt.contains("《加测试》"));
+        assert!(!prompt.contains("寸止") && !prompt.contains("conversation history"));
+    }
+
+    #[test]
+    fn test_render_template_does_not_expand_user_text() {
+        let context = EnhanceContext {
+            original_text: "  解释 {{project_path}} 和 {{memories}} ",
+            selected_options: &["A".to_string(), "B".to_string()],
+            project_path: Some("/work/app"),
+        };
+        let rendered = render_template("《{{original_text}}》{{selected_options}} @ {{project_path}} {{unknown}}", &context);
+        assert_eq!(rendered, "《解释 {{project_path}} 和 {{memories}}》A, B @ /work/app {{unknown}}");
+    }
+
+    #[tokio::test]
+    async fn test_enhance_with_llm_requires_model() {
+        let config = default_enhance_config();
+        let context = EnhanceContext::default();
+        assert!(enhance_with_llm(&config, &context).await.is_err());
+    }
+}
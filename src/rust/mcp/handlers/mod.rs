@@ -1,5 +1,13 @@
+pub mod compose;
+pub mod enhance;
+pub mod policy;
 pub mod popup;
 pub mod response;
+pub mod routing;
 
+pub use compose::*;
+pub use enhance::*;
+pub use policy::*;
 pub use popup::*;
 pub use response::*;
+pub use routing::*;
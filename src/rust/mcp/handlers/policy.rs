@@ -0,0 +1,272 @@
+use chrono::{Local, NaiveTime};
+use globset::Glob;
+use regex::Regex;
+use std::path::Path;
+
+use crate::config::{load_standalone_config, PolicyAction, PolicyRule};
+use crate::mcp::types::{build_continue_response, build_send_response, PopupRequest};
+use crate::{log_debug, log_important};
+
+/// 自动回复响应的来源标识
+pub const POLICY_SOURCE: &str = "policy";
+
+/// 策略匹配结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyDecision {
+    /// 正常询问用户
+    Ask,
+    /// 使用继续提示词自动继续
+    AutoContinue { rule_id: String },
+    /// 自动选择预定义选项
+    SelectOption { rule_id: String, option: String },
+}
+
+/// 按配置的自动回复策略处理请求
+///
+/// 命中自动回复规则时返回构建好的响应（`source` 为 `policy`），否则返回 None
+pub fn apply_reply_policy(request: &PopupRequest) -> Option<String> {
+    let config = match load_standalone_config() {
+        Ok(config) => config,
+        Err(e) => {
+            log_important!(warn, "加载配置失败，跳过自动回复策略: {}", e);
+            return None;
+        }
+    };
+
+    if !config.policy_config.enabled {
+        return None;
+    }
+
+    let decision = evaluate_rules(
+        &config.policy_config.rules,
+        config.reply_config.auto_continue_threshold,
+        request,
+        Local::now().time(),
+    );
+
+    match decision {
+        PolicyDecision::Ask => None,
+        PolicyDecision::AutoContinue { rule_id } => {
+            log_important!(info, "策略规则 {} 自动继续请求 {}", rule_id, request.id);
+            Some(build_continue_response(Some(request.id.clone()), POLICY_SOURCE))
+        }
+        PolicyDecision::SelectOption { rule_id, option } => {
+            log_important!(info, "策略规则 {} 自动选择 \"{}\"，请求 {}", rule_id, option, request.id);
+            Some(build_send_response(
+                None,
+                vec![option],
+                vec![],
+                Some(request.id.clone()),
+                POLICY_SOURCE,
+            ))
+        }
+    }
+}
+
+/// 按顺序匹配规则，第一条命中的规则决定结果
+///
+/// 自动继续仅在消息长度不超过 `auto_continue_threshold` 时生效，
+/// 较长的消息通常包含需要人工判断的内容，仍交给用户处理
+pub fn evaluate_rules(
+    rules: &[PolicyRule],
+    auto_continue_threshold: u32,
+    request: &PopupRequest,
+    now: NaiveTime,
+) -> PolicyDecision {
+    for rule in rules.iter().filter(|r| r.enabled) {
+        if !rule_matches(rule, request, now) {
+            continue;
+        }
+
+        log_debug!("请求 {} 命中策略规则: {}", request.id, rule.name);
+
+        match rule.action {
+            PolicyAction::Ask => return PolicyDecision::Ask,
+            PolicyAction::AutoContinue => {
+                let message_len = request.message.chars().count() as u32;
+                if message_len > auto_continue_threshold {
+                    log_debug!("消息长度 {} 超过自动继续阈值 {}，交给用户处理", message_len, auto_continue_threshold);
+                    return PolicyDecision::Ask;
+                }
+                return PolicyDecision::AutoContinue { rule_id: rule.id.clone() };
+            }
+            PolicyAction::SelectOption => {
+                let available = request.predefined_options.as_deref().unwrap_or(&[]);
+                match &rule.option {
+                    Some(option) if available.contains(option) => {
+                        return PolicyDecision::SelectOption {
+                            rule_id: rule.id.clone(),
+                            option: option.clone(),
+                        };
+                    }
+                    _ => {
+                        // 要选择的选项不在本次请求中，继续匹配后续规则
+                        log_debug!("策略规则 {} 的选项不可用，跳过", rule.name);
+                        continue;
+                    }
+                }
+            }
+            PolicyAction::Unknown => {
+                log_important!(warn, "未知的策略动作，规则: {}", rule.name);
+            }
+        }
+    }
+
+    PolicyDecision::Ask
+}
+
+/// 检查单条规则的所有条件
+fn rule_matches(rule: &PolicyRule, request: &PopupRequest, now: NaiveTime) -> bool {
+    if let Some(pattern) = &rule.message_pattern {
+        match Regex::new(pattern) {
+            Ok(re) => {
+                if !re.is_match(&request.message) {
+                    return false;
+                }
+            }
+            Err(e) => {
+                log_important!(warn, "策略规则 {} 的正则无效: {}", rule.name, e);
+                return false;
+            }
+        }
+    }
+
+    if let Some(pattern) = &rule.project_path {
+        match &request.project_path {
+            Some(project_path) => {
+                if !project_path_matches(pattern, project_path) {
+                    return false;
+                }
+            }
+            None => return false,
+        }
+    }
+
+    if let Some(required) = &rule.options_contain {
+        let available = request.predefined_options.as_deref().unwrap_or(&[]);
+        if !required.iter().all(|option| available.contains(option)) {
+            return false;
+        }
+    }
+
+    let has_time_range = rule.time_start.is_some() || rule.time_end.is_some();
+    if has_time_range && !time_in_range(rule.time_start.as_deref(), rule.time_end.as_deref(), now) {
+        return false;
+    }
+
+    true
+}
+
+/// 项目路径匹配：包含通配符时按glob匹配，否则按路径组件前缀匹配（`/work/app` 不匹配 `/work/app-legacy`）
+fn project_path_matches(pattern: &str, project_path: &str) -> bool {
+    let pattern = pattern.replace('\\', "/");
+    let project_path = project_path.replace('\\', "/");
+
+    if pattern.contains(['*', '?', '[']) {
+        match Glob::new(&pattern) {
+            Ok(glob) => glob.compile_matcher().is_match(&project_path),
+            Err(_) => false,
+        }
+    } else {
+        Path::new(&project_path).starts_with(Path::new(&pattern))
+    }
+}
+
+/// 判断时间是否在 [start, end) 区间内，结束时间早于开始时间时视为跨天
+pub fn time_in_range(start: Option<&str>, end: Option<&str>, now: NaiveTime) -> bool {
+    let parse = |s: &str| NaiveTime::parse_from_str(s.trim(), "%H:%M").ok();
+    let start = start.and_then(parse).unwrap_or(NaiveTime::MIN);
+    let end = end.and_then(parse);
+
+    match end {
+        Some(end) if end < start => now >= start || now < end,
+        Some(end) => now >= start && now < end,
+        None => now >= start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: PolicyAction) -> PolicyRule {
+        PolicyRule {
+            id: "r1".to_string(),
+            name: "test".to_string(),
+            enabled: true,
+            message_pattern: None,
+            project_path: None,
+            options_contain: None,
+            time_start: None,
+            time_end: None,
+            action,
+            option: None,
+        }
+    }
+
+    fn request(message: &str, options: &[&str]) -> PopupRequest {
+        PopupRequest {
+            id: "req".to_string(),
+            message: message.to_string(),
+            predefined_options: Some(options.iter().map(|s| s.to_string()).collect()),
+            is_markdown: true,
+            project_path: Some("/home/dev/projects/app".to_string()),
+            route: None,
+        }
+    }
+
+    fn noon() -> NaiveTime {
+        NaiveTime::from_hms_opt(12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_auto_continue_respects_threshold() {
+        let mut r = rule(PolicyAction::AutoContinue);
+        r.message_pattern = Some("是否继续".to_string());
+        let rules = vec![r];
+
+        let short = request("是否继续？", &[]);
+        assert_eq!(
+            evaluate_rules(&rules, 100, &short, noon()),
+            PolicyDecision::AutoContinue { rule_id: "r1".to_string() }
+        );
+
+        let long = request(&format!("是否继续？{}", "x".repeat(200)), &[]);
+        assert_eq!(evaluate_rules(&rules, 100, &long, noon()), PolicyDecision::Ask);
+    }
+
+    #[test]
+    fn test_select_option_requires_available_option() {
+        let mut r = rule(PolicyAction::SelectOption);
+        r.option = Some("继续".to_string());
+        r.project_path = Some("/home/dev/projects/*".to_string());
+        let rules = vec![r];
+
+        assert_eq!(
+            evaluate_rules(&rules, 1000, &request("下一步？", &["继续", "停止"]), noon()),
+            PolicyDecision::SelectOption { rule_id: "r1".to_string(), option: "继续".to_string() }
+        );
+        assert_eq!(
+            evaluate_rules(&rules, 1000, &request("下一步？", &["停止"]), noon()),
+            PolicyDecision::Ask
+        );
+    }
+
+    #[test]
+    fn test_project_path_prefix_matches_whole_components() {
+        assert!(project_path_matches("/work/app", "/work/app"));
+        assert!(project_path_matches("/work/app/", "/work/app/packages/web"));
+        assert!(!project_path_matches("/work/app", "/work/app-legacy"));
+        assert!(project_path_matches("C:\\work\\app", "C:/work/app/src"));
+        assert!(project_path_matches("/work/app-*", "/work/app-legacy"));
+    }
+
+    #[test]
+    fn test_time_range_across_midnight() {
+        let at = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        assert!(time_in_range(Some("22:00"), Some("06:00"), at(23)));
+        assert!(time_in_range(Some("22:00"), Some("06:00"), at(5)));
+        assert!(!time_in_range(Some("22:00"), Some("06:00"), at(12)));
+        assert!(time_in_range(Some("09:00"), Some("18:00"), at(12)));
+    }
+}
@@ -3,12 +3,40 @@ use std::process::Command;
 use std::fs;
 use std::path::Path;
 
+use crate::config::load_standalone_telegram_config;
+use crate::daemon::try_send_to_daemon;
+use crate::mcp::handlers::BACKEND_TELEGRAM;
 use crate::mcp::types::PopupRequest;
+use crate::log_important;
 
 /// 创建 Tauri 弹窗
 ///
-/// 优先调用与 MCP 服务器同目录的 UI 命令，找不到时使用全局版本
+/// 优先交给常驻的等一下 daemon 处理；daemon 未运行时，
+/// 调用与 MCP 服务器同目录的 UI 命令，找不到时使用全局版本
 pub fn create_tauri_popup(request: &PopupRequest) -> Result<String> {
+    // 纯Telegram模式不需要GUI，保持独立进程处理
+    let telegram_only = match &request.route {
+        Some(route) => route.backend == BACKEND_TELEGRAM,
+        None => load_standalone_telegram_config()
+            .map(|config| config.enabled && config.hide_frontend_popup)
+            .unwrap_or(false),
+    };
+
+    if !telegram_only {
+        match try_send_to_daemon(request) {
+            Ok(Some(response)) => return Ok(response),
+            Ok(None) => {}
+            Err(e) => {
+                log_important!(warn, "daemon处理请求失败，回退到独立进程: {}", e);
+            }
+        }
+    }
+
+    spawn_ui_process(request)
+}
+
+/// 启动独立的等一下进程处理弹窗请求
+fn spawn_ui_process(request: &PopupRequest) -> Result<String> {
     // 创建临时请求文件 - 跨平台适配
     let temp_dir = std::env::temp_dir();
     let temp_file = temp_dir.join(format!("mcp_request_{}.json", request.id));
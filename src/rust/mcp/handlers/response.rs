@@ -1,6 +1,7 @@
 use anyhow::Result;
 use rmcp::{Error as McpError, model::Content};
 
+use crate::i18n::{t, tf, Msg};
 use crate::mcp::types::{McpResponse, McpResponseContent};
 
 /// 解析 MCP 响应内容
@@ -8,7 +9,7 @@ use crate::mcp::types::{McpResponse, McpResponseContent};
 /// 支持新的结构化格式和旧格式的兼容性，并生成适当的 Content 对象
 pub fn parse_mcp_response(response: &str) -> Result<Vec<Content>, McpError> {
     if response.trim() == "CANCELLED" || response.trim() == "用户取消了操作" {
-        return Ok(vec![Content::text("用户取消了操作".to_string())]);
+        return Ok(vec![Content::text(t(Msg::UserCancelled).to_string())]);
     }
 
     // 首先尝试解析为新的结构化格式
@@ -59,10 +60,14 @@ pub fn parse_mcp_response(response: &str) -> Result<Vec<Content>, McpError> {
                                     format!("{:.1} MB", estimated_size as f64 / (1024.0 * 1024.0))
                                 };
 
-                                let image_info = format!(
-                                    "=== 图片 {} ===\n类型: {}\n大小: {}\nBase64 预览: {}\n完整 Base64 长度: {} 字符",
-                                    image_count, source.media_type, size_str, preview, base64_len
-                                );
+                                let image_info = [
+                                    tf(Msg::ImageTitle, &[&image_count]),
+                                    tf(Msg::ImageType, &[&source.media_type]),
+                                    tf(Msg::ImageSize, &[&size_str]),
+                                    tf(Msg::ImagePreview, &[&preview]),
+                                    tf(Msg::ImageBase64Length, &[&base64_len]),
+                                ]
+                                .join("\n");
                                 image_info_parts.push(image_info);
                             }
                         }
@@ -91,10 +96,7 @@ pub fn parse_mcp_response(response: &str) -> Result<Vec<Content>, McpError> {
 
             // 3. 兼容性说明
             if image_count > 0 {
-                all_text_parts.push(format!(
-                    "💡 注意：用户提供了 {} 张图片。如果 AI 助手无法显示图片，图片数据已包含在上述 Base64 信息中。",
-                    image_count
-                ));
+                all_text_parts.push(tf(Msg::ImageNote, &[&image_count]));
             }
 
             // 将所有文本内容合并并添加到结果末尾（图片后面）
@@ -104,7 +106,7 @@ pub fn parse_mcp_response(response: &str) -> Result<Vec<Content>, McpError> {
             }
 
             if result.is_empty() {
-                result.push(Content::text("用户未提供任何内容".to_string()));
+                result.push(Content::text(t(Msg::NoContent).to_string()));
             }
 
             Ok(result)
@@ -123,7 +125,7 @@ fn parse_structured_response(response: McpResponse) -> Result<Vec<Content>, McpE
 
     // 1. 处理选择的选项
     if !response.selected_options.is_empty() {
-        text_parts.push(format!("选择的选项: {}", response.selected_options.join(", ")));
+        text_parts.push(tf(Msg::SelectedOptions, &[&response.selected_options.join(", ")]));
     }
 
     // 2. 处理用户输入文本
@@ -157,14 +159,17 @@ fn parse_structured_response(response: McpResponse) -> Result<Vec<Content>, McpE
             format!("{:.1} MB", estimated_size as f64 / (1024.0 * 1024.0))
         };
 
-        let filename_info = image.filename.as_ref()
-            .map(|f| format!("\n文件名: {}", f))
-            .unwrap_or_default();
-
-        let image_info = format!(
-            "=== 图片 {} ==={}\n类型: {}\n大小: {}\nBase64 预览: {}\n完整 Base64 长度: {} 字符",
-            index + 1, filename_info, image.media_type, size_str, preview, base64_len
-        );
+        let mut image_info_lines = vec![tf(Msg::ImageTitle, &[&(index + 1)])];
+        if let Some(filename) = &image.filename {
+            image_info_lines.push(tf(Msg::ImageFilename, &[filename]));
+        }
+        image_info_lines.extend([
+            tf(Msg::ImageType, &[&image.media_type]),
+            tf(Msg::ImageSize, &[&size_str]),
+            tf(Msg::ImagePreview, &[&preview]),
+            tf(Msg::ImageBase64Length, &[&base64_len]),
+        ]);
+        let image_info = image_info_lines.join("\n");
         image_info_parts.push(image_info);
     }
 
@@ -174,10 +179,7 @@ fn parse_structured_response(response: McpResponse) -> Result<Vec<Content>, McpE
 
     // 5. 添加兼容性说明
     if !response.images.is_empty() {
-        all_text_parts.push(format!(
-            "💡 注意：用户提供了 {} 张图片。如果 AI 助手无法显示图片，图片数据已包含在上述 Base64 信息中。",
-            response.images.len()
-        ));
+        all_text_parts.push(tf(Msg::ImageNote, &[&response.images.len()]));
     }
 
     // 6. 将文本内容添加到结果中（图片后面）
@@ -188,7 +190,7 @@ fn parse_structured_response(response: McpResponse) -> Result<Vec<Content>, McpE
 
     // 7. 如果没有任何内容，添加默认响应
     if result.is_empty() {
-        result.push(Content::text("用户未提供任何内容".to_string()));
+        result.push(Content::text(t(Msg::NoContent).to_string()));
     }
 
     Ok(result)
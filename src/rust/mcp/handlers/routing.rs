@@ -0,0 +1,187 @@
+use chrono::{Datelike, Local, NaiveDateTime};
+
+use crate::config::{load_standalone_config, AppConfig, RoutingSchedule, TelegramConfig};
+use crate::mcp::handlers::time_in_range;
+use crate::mcp::types::RouteDecision;
+use crate::utils::system_idle_seconds;
+use crate::{log_debug, log_important};
+
+/// 仅使用前端弹窗
+pub const BACKEND_POPUP: &str = "popup";
+/// 仅使用Telegram
+pub const BACKEND_TELEGRAM: &str = "telegram";
+/// 前端弹窗和Telegram同时使用
+pub const BACKEND_BOTH: &str = "both";
+
+/// 按当前时间和桌面空闲状态计算交互路由
+///
+/// 配置加载失败时回退到原有的Telegram配置行为
+pub fn resolve_current_route() -> RouteDecision {
+    let config = match load_standalone_config() {
+        Ok(config) => config,
+        Err(e) => {
+            log_important!(warn, "加载配置失败，使用默认交互路由: {}", e);
+            return RouteDecision {
+                backend: BACKEND_POPUP.to_string(),
+                mute_audio: false,
+            };
+        }
+    };
+
+    // 只有配置了空闲转发才查询空闲时间，避免每次请求都调用外部命令
+    let idle_secs = if config.routing_config.enabled
+        && config.routing_config.idle_to_telegram_minutes.is_some()
+    {
+        system_idle_seconds()
+    } else {
+        None
+    };
+
+    let decision = resolve_route(&config, Local::now().naive_local(), idle_secs);
+    log_debug!("交互路由: {:?}", decision);
+    decision
+}
+
+/// 计算交互路由
+///
+/// 优先级：命中的免打扰时间段 > 默认Telegram配置；桌面空闲超过阈值时改用Telegram。
+/// 需要Telegram但Telegram未配置完整时回退到前端弹窗
+pub fn resolve_route(
+    config: &AppConfig,
+    now: NaiveDateTime,
+    idle_secs: Option<u64>,
+) -> RouteDecision {
+    let telegram = &config.telegram_config;
+    let mut backend = default_backend(telegram).to_string();
+    let mut mute_audio = false;
+
+    if config.routing_config.enabled {
+        if let Some(schedule) = config
+            .routing_config
+            .schedules
+            .iter()
+            .find(|s| s.enabled && schedule_matches(s, now))
+        {
+            log_debug!("命中免打扰时间段: {}", schedule.name);
+            if let Some(schedule_backend) = &schedule.backend {
+                backend = schedule_backend.clone();
+            }
+            mute_audio = schedule.mute_audio;
+        }
+
+        if let (Some(minutes), Some(idle)) = (config.routing_config.idle_to_telegram_minutes, idle_secs) {
+            if idle >= u64::from(minutes) * 60 {
+                log_debug!("桌面已空闲 {} 秒，改用Telegram", idle);
+                backend = BACKEND_TELEGRAM.to_string();
+            }
+        }
+    }
+
+    if !matches!(backend.as_str(), BACKEND_POPUP | BACKEND_TELEGRAM | BACKEND_BOTH) {
+        log_important!(warn, "未知的交互方式: {}，使用前端弹窗", backend);
+        backend = BACKEND_POPUP.to_string();
+    }
+
+    if backend != BACKEND_POPUP && !telegram_ready(telegram) {
+        if backend == BACKEND_TELEGRAM {
+            log_important!(warn, "Telegram未配置完整，回退到前端弹窗");
+        }
+        backend = BACKEND_POPUP.to_string();
+    }
+
+    RouteDecision { backend, mute_audio }
+}
+
+/// 未配置路由时沿用Telegram设置决定交互方式
+fn default_backend(telegram: &TelegramConfig) -> &'static str {
+    match (telegram.enabled, telegram.hide_frontend_popup) {
+        (true, true) => BACKEND_TELEGRAM,
+        (true, false) => BACKEND_BOTH,
+        _ => BACKEND_POPUP,
+    }
+}
+
+fn telegram_ready(telegram: &TelegramConfig) -> bool {
+    telegram.enabled && !telegram.bot_token.trim().is_empty() && !telegram.chat_id.trim().is_empty()
+}
+
+/// 检查时间段是否覆盖当前时间（星期 + 时段）
+fn schedule_matches(schedule: &RoutingSchedule, now: NaiveDateTime) -> bool {
+    let weekday = now.weekday().number_from_monday();
+    if !schedule.weekdays.is_empty() && !schedule.weekdays.contains(&weekday) {
+        return false;
+    }
+
+    time_in_range(schedule.time_start.as_deref(), schedule.time_end.as_deref(), now.time())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn config(hide_frontend_popup: bool) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.telegram_config.enabled = true;
+        config.telegram_config.hide_frontend_popup = hide_frontend_popup;
+        config.telegram_config.bot_token = "token".to_string();
+        config.telegram_config.chat_id = "chat".to_string();
+        config.routing_config.enabled = true;
+        config.routing_config.idle_to_telegram_minutes = Some(10);
+        config.routing_config.schedules = vec![RoutingSchedule {
+            id: "night".to_string(),
+            name: "夜间".to_string(),
+            enabled: true,
+            weekdays: vec![1, 2, 3, 4, 5],
+            time_start: Some("22:00".to_string()),
+            time_end: Some("08:00".to_string()),
+            backend: Some(BACKEND_TELEGRAM.to_string()),
+            mute_audio: true,
+        }];
+        config
+    }
+
+    /// 2024-01-01 是周一
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_time(chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+    }
+
+    #[test]
+    fn test_resolve_route_table() {
+        let cases = [
+            // (说明, 时间, 空闲秒数, 期望后端, 期望静音)
+            ("工作时间不空闲", at(1, "10:00"), Some(0), BACKEND_BOTH, false),
+            ("免打扰开始", at(1, "22:00"), None, BACKEND_TELEGRAM, true),
+            ("免打扰跨天", at(2, "07:59"), Some(0), BACKEND_TELEGRAM, true),
+            ("免打扰结束", at(2, "08:00"), Some(0), BACKEND_BOTH, false),
+            ("周末不在免打扰星期内", at(6, "23:00"), Some(0), BACKEND_BOTH, false),
+            ("空闲未到阈值", at(1, "10:00"), Some(599), BACKEND_BOTH, false),
+            ("空闲达到阈值", at(1, "10:00"), Some(600), BACKEND_TELEGRAM, false),
+            ("无法获取空闲时间", at(1, "10:00"), None, BACKEND_BOTH, false),
+        ];
+
+        let config = config(false);
+        for (name, now, idle, backend, mute_audio) in cases {
+            let decision = resolve_route(&config, now, idle);
+            assert_eq!(decision.backend, backend, "{}", name);
+            assert_eq!(decision.mute_audio, mute_audio, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_resolve_route_falls_back_without_telegram() {
+        let mut config = config(true);
+        assert_eq!(resolve_route(&config, at(1, "10:00"), None).backend, BACKEND_TELEGRAM);
+
+        config.routing_config.enabled = false;
+        assert_eq!(resolve_route(&config, at(1, "23:00"), Some(3600)).backend, BACKEND_TELEGRAM);
+
+        config.routing_config.enabled = true;
+        config.telegram_config.bot_token.clear();
+        let decision = resolve_route(&config, at(1, "23:00"), Some(3600));
+        assert_eq!(decision.backend, BACKEND_POPUP);
+        assert!(decision.mute_audio);
+    }
+}
@@ -8,8 +8,10 @@ use rmcp::{
 use std::collections::HashMap;
 
 use super::tools::{InteractionTool, MemoryTool, AcemcpTool};
-use super::types::{ZhiRequest, JiyiRequest};
+use super::tools::memory::{AgentRulesFile, MemoryScope};
+use super::types::{ZhiRequest, JiyiAction, JiyiRequest};
 use crate::config::load_standalone_config;
+use crate::i18n::{reload_locale, t, tf, Msg};
 use crate::{log_important, log_debug};
 
 #[derive(Clone)]
@@ -64,7 +66,7 @@ impl ServerHandler for ZhiServer {
                 name: "Zhi-mcp".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
             },
-            instructions: Some("Zhi 智能代码审查工具，支持交互式对话和记忆管理".to_string()),
+            instructions: Some(t(Msg::ServerInstructions).to_string()),
         }
     }
 
@@ -84,6 +86,9 @@ impl ServerHandler for ZhiServer {
         use std::sync::Arc;
         use std::borrow::Cow;
 
+        // 每次列出工具时重新读取语言设置，与工具启用状态一致
+        reload_locale();
+
         let mut tools = Vec::new();
 
         // 寸止工具始终可用（必需工具）
@@ -92,16 +97,20 @@ impl ServerHandler for ZhiServer {
             "properties": {
                 "message": {
                     "type": "string",
-                    "description": "要显示给用户的消息"
+                    "description": t(Msg::ZhiParamMessage)
                 },
                 "predefined_options": {
                     "type": "array",
                     "items": {"type": "string"},
-                    "description": "预定义的选项列表（可选）"
+                    "description": t(Msg::ZhiParamOptions)
                 },
                 "is_markdown": {
                     "type": "boolean",
-                    "description": "消息是否为Markdown格式，默认为true"
+                    "description": t(Msg::ZhiParamMarkdown)
+                },
+                "project_path": {
+                    "type": "string",
+                    "description": t(Msg::ZhiParamProjectPath)
                 }
             },
             "required": ["message"]
@@ -110,7 +119,7 @@ impl ServerHandler for ZhiServer {
         if let serde_json::Value::Object(schema_map) = zhi_schema {
             tools.push(Tool {
                 name: Cow::Borrowed("zhi"),
-                description: Some(Cow::Borrowed("智能代码审查交互工具，支持预定义选项、自由文本输入和图片上传")),
+                description: Some(Cow::Borrowed(t(Msg::ZhiDescription))),
                 input_schema: Arc::new(schema_map),
                 annotations: None,
             });
@@ -123,19 +132,79 @@ impl ServerHandler for ZhiServer {
                 "properties": {
                     "action": {
                         "type": "string",
-                        "description": "操作类型：记忆(添加记忆), 回忆(获取项目信息)"
+                        "enum": JiyiAction::accepted_names(),
+                        "description": t(Msg::JiParamAction)
                     },
                     "project_path": {
                         "type": "string",
-                        "description": "项目路径（必需）"
+                        "description": t(Msg::JiParamProjectPath)
                     },
                     "content": {
                         "type": "string",
-                        "description": "记忆内容（记忆操作时必需）"
+                        "description": t(Msg::JiParamContent)
                     },
                     "category": {
                         "type": "string",
-                        "description": "记忆分类：rule(规范规则), preference(用户偏好), pattern(最佳实践), context(项目上下文)"
+                        "description": t(Msg::JiParamCategory)
+                    },
+                    "id": {
+                        "type": "string",
+                        "description": t(Msg::JiParamId)
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": t(Msg::JiParamQuery)
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": t(Msg::JiParamLimit)
+                    },
+                    "on_duplicate": {
+                        "type": "string",
+                        "enum": ["reject", "merge", "allow"],
+                        "description": t(Msg::JiParamOnDuplicate)
+                    },
+                    "scope": {
+                        "type": "string",
+                        "enum": MemoryScope::ALL.map(|s| s.key()),
+                        "description": t(Msg::JiParamScope)
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": t(Msg::JiParamTags)
+                    },
+                    "plan_id": {
+                        "type": "string",
+                        "description": t(Msg::JiParamPlanId)
+                    },
+                    "rules_file": {
+                        "type": "string",
+                        "enum": AgentRulesFile::ALL.map(|f| f.key()),
+                        "description": t(Msg::JiParamRulesFile)
+                    },
+                    "max_chars": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": t(Msg::JiParamMaxChars)
+                    },
+                    "max_tokens": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": t(Msg::JiParamMaxTokens)
+                    },
+                    "branch": {
+                        "type": "string",
+                        "description": t(Msg::JiParamBranch)
+                    },
+                    "ttl": {
+                        "type": "string",
+                        "description": t(Msg::JiParamTtl)
+                    },
+                    "expires_at": {
+                        "type": "string",
+                        "description": t(Msg::JiParamExpiresAt)
                     }
                 },
                 "required": ["action", "project_path"]
@@ -144,7 +213,7 @@ impl ServerHandler for ZhiServer {
             if let serde_json::Value::Object(schema_map) = ji_schema {
                 tools.push(Tool {
                     name: Cow::Borrowed("ji"),
-                    description: Some(Cow::Borrowed("全局记忆管理工具，用于存储和管理重要的开发规范、用户偏好和最佳实践")),
+                    description: Some(Cow::Borrowed(t(Msg::JiDescription))),
                     input_schema: Arc::new(schema_map),
                     annotations: None,
                 });
@@ -170,6 +239,7 @@ impl ServerHandler for ZhiServer {
         _context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         log_debug!("收到工具调用请求: {}", request.name);
+        reload_locale();
 
         match request.name.as_ref() {
             "zhi" => {
@@ -179,7 +249,7 @@ impl ServerHandler for ZhiServer {
                     .unwrap_or(serde_json::Value::Object(serde_json::Map::new()));
 
                 let zhi_request: ZhiRequest = serde_json::from_value(arguments_value)
-                    .map_err(|e| McpError::invalid_params(format!("参数解析失败: {}", e), None))?;
+                    .map_err(|e| McpError::invalid_params(tf(Msg::ParamParseFailed, &[&e]), None))?;
 
                 // 调用寸止工具
                 InteractionTool::zhi(zhi_request).await
@@ -188,7 +258,7 @@ impl ServerHandler for ZhiServer {
                 // 检查记忆管理工具是否启用
                 if !self.is_tool_enabled("ji") {
                     return Err(McpError::internal_error(
-                        "记忆管理工具已被禁用".to_string(),
+                        t(Msg::JiDisabled).to_string(),
                         None
                     ));
                 }
@@ -199,7 +269,7 @@ impl ServerHandler for ZhiServer {
                     .unwrap_or(serde_json::Value::Object(serde_json::Map::new()));
 
                 let ji_request: JiyiRequest = serde_json::from_value(arguments_value)
-                    .map_err(|e| McpError::invalid_params(format!("参数解析失败: {}", e), None))?;
+                    .map_err(|e| McpError::invalid_params(tf(Msg::ParamParseFailed, &[&e]), None))?;
 
                 // 调用记忆工具
                 MemoryTool::jiyi(ji_request).await
@@ -208,7 +278,7 @@ impl ServerHandler for ZhiServer {
                 // 检查代码搜索工具是否启用
                 if !self.is_tool_enabled("sou") {
                     return Err(McpError::internal_error(
-                        "代码搜索工具已被禁用".to_string(),
+                        t(Msg::SouDisabled).to_string(),
                         None
                     ));
                 }
@@ -220,14 +290,14 @@ impl ServerHandler for ZhiServer {
 
                 // 使用acemcp模块中的AcemcpRequest类型
                 let acemcp_request: crate::mcp::tools::acemcp::types::AcemcpRequest = serde_json::from_value(arguments_value)
-                    .map_err(|e| McpError::invalid_params(format!("参数解析失败: {}", e), None))?;
+                    .map_err(|e| McpError::invalid_params(tf(Msg::ParamParseFailed, &[&e]), None))?;
 
                 // 调用代码搜索工具
                 AcemcpTool::search_context(acemcp_request).await
             }
             _ => {
                 Err(McpError::invalid_request(
-                    format!("未知的工具: {}", request.name),
+                    tf(Msg::UnknownTool, &[&request.name]),
                     None
                 ))
             }
@@ -17,6 +17,7 @@ use encoding_rs::{GBK, WINDOWS_1252, UTF_8};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 
 use super::types::{AcemcpRequest, AcemcpConfig};
+use crate::i18n::{t, Msg};
 use crate::log_debug;
 use crate::log_important;
 
@@ -72,11 +73,11 @@ impl AcemcpTool {
             "properties": {
                 "project_root_path": {
                     "type": "string",
-                    "description": "项目根目录的绝对路径，使用正斜杠(/)作为分隔符。例如：C:/Users/username/projects/myproject"
+                    "description": t(Msg::SouParamProjectRoot)
                 },
                 "query": {
                     "type": "string",
-                    "description": "用于查找相关代码上下文的自然语言搜索查询。此工具执行语义搜索并返回与查询匹配的代码片段。例如：'日志配置设置初始化logger'（查找日志设置代码）、'用户认证登录'（查找认证相关代码）、'数据库连接池'（查找数据库连接代码）、'错误处理异常'（查找错误处理模式）、'API端点路由'（查找API路由定义）。工具返回带有文件路径和行号的格式化文本片段，显示相关代码的位置。"
+                    "description": t(Msg::SouParamQuery)
                 }
             },
             "required": ["project_root_path", "query"]
@@ -85,7 +86,7 @@ impl AcemcpTool {
         if let serde_json::Value::Object(schema_map) = schema {
             Tool {
                 name: Cow::Borrowed("sou"),
-                description: Some(Cow::Borrowed("基于查询在特定项目中搜索相关的代码上下文。此工具在搜索前自动执行增量索引，确保结果始终是最新的。返回代码库中与查询语义相关的格式化文本片段。")),
+                description: Some(Cow::Borrowed(t(Msg::SouDescription))),
                 input_schema: Arc::new(schema_map),
                 annotations: None,
             }
@@ -117,7 +118,7 @@ fn normalize_base_url(input: &str) -> String {
     url
 }
 
-async fn retry_request<F, Fut, T>(mut f: F, max_retries: usize, base_delay_secs: f64) -> anyhow::Result<T>
+pub(crate) async fn retry_request<F, Fut, T>(mut f: F, max_retries: usize, base_delay_secs: f64) -> anyhow::Result<T>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = anyhow::Result<T>>,
@@ -2,7 +2,7 @@ use anyhow::Result;
 use rmcp::{Error as McpError, model::*};
 
 use crate::mcp::{ZhiRequest, PopupRequest};
-use crate::mcp::handlers::{create_tauri_popup, parse_mcp_response};
+use crate::mcp::handlers::{apply_prompt_composition, apply_reply_policy, create_tauri_popup, parse_mcp_response, resolve_current_route};
 use crate::mcp::utils::{generate_request_id, popup_error};
 
 /// 智能代码审查交互工具
@@ -15,7 +15,7 @@ impl InteractionTool {
     pub async fn zhi(
         request: ZhiRequest,
     ) -> Result<CallToolResult, McpError> {
-        let popup_request = PopupRequest {
+        let mut popup_request = PopupRequest {
             id: generate_request_id(),
             message: request.message,
             predefined_options: if request.predefined_options.is_empty() {
@@ -24,10 +24,24 @@ impl InteractionTool {
                 Some(request.predefined_options)
             },
             is_markdown: request.is_markdown,
+            project_path: request.project_path,
+            route: None,
         };
 
+        // 命中自动回复策略时直接回答，不打扰用户
+        if let Some(response) = apply_reply_policy(&popup_request) {
+            let response = apply_prompt_composition(&response);
+            let content = parse_mcp_response(&response)?;
+            return Ok(CallToolResult::success(content));
+        }
+
+        // 按免打扰时间段和在场状态决定交互方式
+        popup_request.route = Some(resolve_current_route());
+
         match create_tauri_popup(&popup_request) {
             Ok(response) => {
+                // 统一追加上下文内容，保证各交互方式的回复一致
+                let response = apply_prompt_composition(&response);
                 // 解析响应内容，支持文本和图片
                 let content = parse_mcp_response(&response)?;
                 Ok(CallToolResult::success(content))
@@ -0,0 +1,345 @@
+//! 其他智能体规则文件的导入导出
+//!
+//! 导入时读取 `CLAUDE.md`、`AGENTS.md` 等文件中的列表项，按所在标题归入记忆分类；
+//! 导出时把记忆渲染到文件内带标记的托管区块中，重复导出只会替换该区块，区块外的内容保持不变
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::category::CategoryRegistry;
+use super::expiry::Expiry;
+use super::manager::{AddOutcome, MemoryManager};
+use super::store::atomic_write;
+use super::types::{DuplicatePolicy, MemoryCategory, MemoryEntry};
+
+/// 托管区块起始标记
+const BLOCK_START: &str = "<!-- cunzhi-memory:start";
+/// 托管区块结束标记
+const BLOCK_END: &str = "<!-- cunzhi-memory:end -->";
+
+/// 支持的规则文件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AgentRulesFile {
+    Claude,
+    Agents,
+    Cursor,
+    Copilot,
+    Windsurf,
+}
+
+impl AgentRulesFile {
+    pub const ALL: [AgentRulesFile; 5] = [
+        AgentRulesFile::Claude,
+        AgentRulesFile::Agents,
+        AgentRulesFile::Cursor,
+        AgentRulesFile::Copilot,
+        AgentRulesFile::Windsurf,
+    ];
+
+    /// 文件在工具参数中使用的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            AgentRulesFile::Claude => "claude",
+            AgentRulesFile::Agents => "agents",
+            AgentRulesFile::Cursor => "cursor",
+            AgentRulesFile::Copilot => "copilot",
+            AgentRulesFile::Windsurf => "windsurf",
+        }
+    }
+
+    /// 根据标识或文件名解析，忽略大小写
+    pub fn from_key(key: &str) -> Option<Self> {
+        let key = key.trim();
+        Self::ALL
+            .into_iter()
+            .find(|file| file.key().eq_ignore_ascii_case(key) || file.relative_path().eq_ignore_ascii_case(key))
+    }
+
+    /// 相对于项目根目录的路径
+    pub fn relative_path(&self) -> &'static str {
+        match self {
+            AgentRulesFile::Claude => "CLAUDE.md",
+            AgentRulesFile::Agents => "AGENTS.md",
+            AgentRulesFile::Cursor => ".cursorrules",
+            AgentRulesFile::Copilot => ".github/copilot-instructions.md",
+            AgentRulesFile::Windsurf => ".windsurfrules",
+        }
+    }
+
+    pub fn path(&self, project_root: &Path) -> PathBuf {
+        project_root.join(self.relative_path())
+    }
+
+    /// 项目中已存在的规则文件
+    pub fn existing(project_root: &Path) -> Vec<AgentRulesFile> {
+        Self::ALL.into_iter().filter(|file| file.path(project_root).is_file()).collect()
+    }
+}
+
+/// 从规则文件解析出的一条记忆
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRule {
+    pub category: MemoryCategory,
+    pub content: String,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RulesImportReport {
+    pub file: String,
+    pub imported: usize,
+    /// 已有相似记忆而跳过的条目数
+    pub skipped: usize,
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RulesExportReport {
+    pub file: String,
+    pub exported: usize,
+    /// 文件原本不存在
+    pub created: bool,
+}
+
+/// 标题关键词与分类的对应关系，未命中时归为规范
+const HEADING_KEYWORDS: &[(MemoryCategory, &[&str])] = &[
+    (MemoryCategory::PREFERENCE, &["prefer", "偏好", "style", "风格", "tone", "语气"]),
+    (MemoryCategory::PATTERN, &["pattern", "模式", "best practice", "最佳实践", "example", "示例", "workflow", "流程"]),
+    (MemoryCategory::CONTEXT, &["context", "背景", "overview", "概述", "architecture", "架构", "structure", "结构", "about", "简介"]),
+    (MemoryCategory::RULE, &["rule", "规则", "规范", "convention", "约定", "guideline", "准则", "要求"]),
+];
+
+/// 根据标题推断分类，优先匹配已定义分类的标识和标题
+fn category_for_heading(heading: &str, categories: &CategoryRegistry) -> Option<MemoryCategory> {
+    let lower = heading.to_lowercase();
+    for category in categories.all() {
+        let title = categories.title(&category).to_lowercase();
+        if lower == category.key() || lower == title || lower == categories.label(&category).to_lowercase() {
+            return Some(category);
+        }
+    }
+
+    HEADING_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|keyword| lower.contains(keyword)))
+        .map(|(category, _)| category.clone())
+}
+
+/// 解析列表项，返回去掉列表符号后的内容
+fn parse_bullet(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let rest = if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")).or_else(|| trimmed.strip_prefix("+ ")) {
+        rest
+    } else {
+        let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+        let rest = &trimmed[digits..];
+        if digits == 0 {
+            return None;
+        }
+        rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))?
+    };
+
+    // 去掉任务列表的复选框
+    let rest = ["[ ] ", "[x] ", "[X] "].iter().find_map(|box_| rest.strip_prefix(box_)).unwrap_or(rest);
+    Some(rest.trim())
+}
+
+/// 解析规则文件中的列表项
+///
+/// 标题决定其下列表项的分类，无法归类的标题沿用上级标题的分类，二级及以下的此类标题会作为内容前缀保留；
+/// 缩进的子列表和续行并入上一条，代码块和本工具导出的托管区块会被跳过
+pub fn parse_rules(content: &str, categories: &CategoryRegistry) -> Vec<ImportedRule> {
+    let mut rules: Vec<ImportedRule> = Vec::new();
+    let mut current: Option<ImportedRule> = None;
+    let mut category = MemoryCategory::RULE;
+    let mut topic: Option<String> = None;
+    let mut headings: Vec<(usize, Option<MemoryCategory>)> = Vec::new();
+    let mut in_code = false;
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with(BLOCK_START) {
+            in_block = true;
+            continue;
+        }
+        if in_block {
+            in_block = trimmed != BLOCK_END;
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            rules.extend(current.take());
+            continue;
+        }
+
+        if trimmed.starts_with('#') {
+            rules.extend(current.take());
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            let heading = trimmed[level..].trim();
+            let matched = category_for_heading(heading, categories);
+            topic = (matched.is_none() && level >= 2 && !heading.is_empty()).then(|| heading.to_string());
+
+            // 无法归类的标题沿用上级标题的分类
+            headings.retain(|(outer, _)| *outer < level);
+            headings.push((level, matched));
+            category = headings
+                .iter()
+                .rev()
+                .find_map(|(_, matched)| matched.clone())
+                .unwrap_or(MemoryCategory::RULE);
+            continue;
+        }
+
+        let indented = line.starts_with(' ') || line.starts_with('\t');
+        match (parse_bullet(line), current.as_mut()) {
+            (_, Some(rule)) if indented => {
+                rule.content.push('\n');
+                rule.content.push_str(trimmed);
+            }
+            (Some(text), _) if !text.is_empty() => {
+                rules.extend(current.take());
+                let content = match &topic {
+                    Some(topic) => format!("{}: {}", topic, text),
+                    None => text.to_string(),
+                };
+                current = Some(ImportedRule { category: category.clone(), content });
+            }
+            // 列表项后紧跟的普通文本视为续行
+            (None, Some(rule)) => {
+                rule.content.push(' ');
+                rule.content.push_str(trimmed);
+            }
+            _ => {}
+        }
+    }
+
+    rules.extend(current);
+    rules
+}
+
+/// 将记忆渲染为托管区块
+pub fn render_block(memories: &[MemoryEntry], categories: &CategoryRegistry) -> String {
+    let mut block = format!("{} 由寸止根据项目记忆生成，重新导出会覆盖此区块，请通过记忆工具修改 -->\n", BLOCK_START);
+    for category in categories.all() {
+        let items: Vec<&MemoryEntry> = memories.iter().filter(|m| m.category == category).collect();
+        if items.is_empty() {
+            continue;
+        }
+
+        block.push_str(&format!("\n## {}\n\n", categories.title(&category)));
+        for item in items {
+            let mut lines = item.content.trim().lines();
+            block.push_str(&format!("- {}\n", lines.next().unwrap_or_default().trim()));
+            for line in lines.filter(|line| !line.trim().is_empty()) {
+                block.push_str(&format!("  {}\n", line.trim()));
+            }
+        }
+    }
+    block.push('\n');
+    block.push_str(BLOCK_END);
+    block.push('\n');
+    block
+}
+
+/// 用新的托管区块替换文件中已有的区块，没有区块时追加到末尾
+pub fn replace_block(existing: &str, block: &str) -> String {
+    let start = existing.find(BLOCK_START);
+    let end = start.and_then(|start| existing[start..].find(BLOCK_END).map(|offset| start + offset + BLOCK_END.len()));
+
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            let rest = existing[end..].strip_prefix('\n').unwrap_or(&existing[end..]);
+            format!("{}{}{}", &existing[..start], block, rest)
+        }
+        _ if existing.trim().is_empty() => block.to_string(),
+        _ => format!("{}\n\n{}", existing.trim_end(), block),
+    }
+}
+
+/// 从规则文件导入记忆，已有相似记忆的条目会被跳过，因此可以重复导入
+pub fn import_rules(manager: &MemoryManager, project_root: &Path, file: AgentRulesFile) -> Result<RulesImportReport> {
+    let path = file.path(project_root);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("无法读取规则文件: {}\n错误: {}", path.display(), e))?;
+
+    let mut report = RulesImportReport {
+        file: file.relative_path().to_string(),
+        ..Default::default()
+    };
+    for rule in parse_rules(&content, manager.categories()) {
+        let category = if manager.categories().contains(&rule.category) { rule.category } else { MemoryCategory::RULE };
+        match manager.add_memory_checked(&rule.content, category, Vec::new(), DuplicatePolicy::Reject, None, Expiry::Never)?.0 {
+            AddOutcome::Added { .. } => report.imported += 1,
+            AddOutcome::Duplicate(_) | AddOutcome::Merged(_) => report.skipped += 1,
+        }
+    }
+    Ok(report)
+}
+
+/// 将记忆导出到规则文件的托管区块
+pub fn export_rules(manager: &MemoryManager, project_root: &Path, file: AgentRulesFile) -> Result<RulesExportReport> {
+    let path = file.path(project_root);
+    let created = !path.exists();
+    let existing = if created { String::new() } else { fs::read_to_string(&path)? };
+
+    // 规则文件长期对所有分支生效，分支记忆和会过期的记忆不导出
+    let memories: Vec<MemoryEntry> = manager
+        .get_memories_in_priority_order()?
+        .into_iter()
+        .filter(|memory| memory.branch.is_none() && memory.expires_at.is_none())
+        .collect();
+    let content = replace_block(&existing, &render_block(&memories, manager.categories()));
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    atomic_write(&path, content)?;
+
+    Ok(RulesExportReport {
+        file: file.relative_path().to_string(),
+        exported: memories.len(),
+        created,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules_categorizes_by_heading() {
+        let content = "# Project\n\n## Code Style Preferences\n- Prefer early returns\n\n## Testing\n1. Run `cargo test`\n   before pushing\n- [x] Keep tests fast\n\n```\n- not a rule\n```\n\n## Architecture\n* Tauri app with a Vue frontend\n  - MCP server in Rust\n";
+        let rules = parse_rules(content, &CategoryRegistry::builtin());
+
+        let expected = vec![
+            (MemoryCategory::PREFERENCE, "Prefer early returns"),
+            (MemoryCategory::RULE, "Testing: Run `cargo test`\nbefore pushing"),
+            (MemoryCategory::RULE, "Testing: Keep tests fast"),
+            (MemoryCategory::CONTEXT, "Tauri app with a Vue frontend\n- MCP server in Rust"),
+        ];
+        let actual: Vec<(MemoryCategory, &str)> = rules.iter().map(|r| (r.category.clone(), r.content.as_str())).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_replace_block_is_idempotent_and_skipped_on_import() {
+        let block = replace_block("", &render_block(&[], &CategoryRegistry::builtin()));
+        let original = format!("# Rules\n\n- Use pnpm\n\n{}\nFooter\n", block);
+
+        let updated = replace_block(&original, &block);
+        assert_eq!(updated, original);
+        assert_eq!(parse_rules(&updated, &CategoryRegistry::builtin()).len(), 1);
+        assert!(replace_block("# Rules\n", &block).starts_with("# Rules\n\n<!-- cunzhi-memory:start"));
+    }
+}
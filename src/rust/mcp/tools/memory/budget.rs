@@ -0,0 +1,159 @@
+//! 召回输出的长度预算
+//!
+//! 记忆较多的项目里，完整的召回结果会占用大量上下文。按分类召回优先级依次填充，
+//! 同一分类内按更新时间或检索相关度排列，超出预算的条目只在末尾说明数量和获取方式
+
+use super::category::CategoryRegistry;
+use super::manager::{
+    entry_label, overview_heading, overview_prefix, summarize_memories, OVERVIEW_CATEGORY_SEPARATOR, OVERVIEW_ITEM_SEPARATOR,
+};
+use super::types::{MemoryCategory, MemoryEntry};
+use crate::i18n::{t, tf, Msg};
+
+/// 召回输出的长度上限
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecallBudget {
+    Unlimited,
+    /// 按字符数计算
+    Chars(usize),
+    /// 按估算的 token 数计算
+    Tokens(usize),
+}
+
+impl RecallBudget {
+    /// 根据请求参数确定预算，两者都提供时以字符数为准，都未提供时使用配置中的默认 token 预算，0 表示不限制
+    pub fn resolve(max_chars: Option<usize>, max_tokens: Option<usize>, default_tokens: usize) -> Self {
+        match (max_chars, max_tokens) {
+            (Some(0), _) | (None, Some(0)) => RecallBudget::Unlimited,
+            (Some(chars), _) => RecallBudget::Chars(chars),
+            (None, Some(tokens)) => RecallBudget::Tokens(tokens),
+            (None, None) if default_tokens == 0 => RecallBudget::Unlimited,
+            (None, None) => RecallBudget::Tokens(default_tokens),
+        }
+    }
+
+    /// 文本在该预算下的开销
+    pub fn cost(&self, text: &str) -> usize {
+        match self {
+            RecallBudget::Unlimited => 0,
+            RecallBudget::Chars(_) => text.chars().count(),
+            RecallBudget::Tokens(_) => estimate_tokens(text),
+        }
+    }
+
+    fn limit(&self) -> Option<usize> {
+        match self {
+            RecallBudget::Unlimited => None,
+            RecallBudget::Chars(limit) | RecallBudget::Tokens(limit) => Some(*limit),
+        }
+    }
+}
+
+/// 粗略估算 token 数：中日韩文字约每字一个 token，其他字符约每四个一个 token
+pub fn estimate_tokens(text: &str) -> usize {
+    let cjk = text.chars().filter(|c| !c.is_ascii() && c.is_alphanumeric()).count();
+    let other = text.chars().count() - cjk;
+    cjk + other.div_ceil(4)
+}
+
+/// 在预算内按顺序选取条目，返回 (保留, 省略)
+///
+/// 条目需已按期望的填充顺序排列。遇到第一条放不下的条目即停止，保证保留的条目优先级都不低于省略的条目
+pub fn fill(memories: Vec<MemoryEntry>, categories: &CategoryRegistry, budget: RecallBudget) -> (Vec<MemoryEntry>, Vec<MemoryEntry>) {
+    let Some(limit) = budget.limit() else {
+        return (memories, Vec::new());
+    };
+
+    // 与 summarize_memories 的输出结构一致：总览前缀、每个分类的标题、条目及分隔符
+    let labelled = memories.first().is_some_and(|first| memories.iter().any(|m| m.scope != first.scope));
+    let mut used = budget.cost(overview_prefix());
+    let mut seen_categories: Vec<MemoryCategory> = Vec::new();
+    let mut included = Vec::new();
+    let mut remaining = memories.into_iter();
+
+    for memory in remaining.by_ref() {
+        let mut cost = budget.cost(&memory.content.split_whitespace().collect::<Vec<&str>>().join(" ")) + budget.cost(OVERVIEW_ITEM_SEPARATOR);
+        cost += budget.cost(&entry_label(&memory, labelled));
+        if !seen_categories.contains(&memory.category) {
+            cost += budget.cost(OVERVIEW_CATEGORY_SEPARATOR) + budget.cost(&overview_heading(&categories.label(&memory.category)));
+        }
+
+        if used + cost > limit {
+            let mut omitted = vec![memory];
+            omitted.extend(remaining);
+            return (included, omitted);
+        }
+
+        used += cost;
+        if !seen_categories.contains(&memory.category) {
+            seen_categories.push(memory.category.clone());
+        }
+        included.push(memory);
+    }
+
+    (included, Vec::new())
+}
+
+/// 说明被省略的条目数量和获取方式
+pub fn truncation_note(omitted: &[MemoryEntry], categories: &CategoryRegistry) -> String {
+    let counts: Vec<String> = categories
+        .all()
+        .filter_map(|category| {
+            let count = omitted.iter().filter(|m| m.category == category).count();
+            (count > 0).then(|| tf(Msg::JiTruncatedCount, &[&categories.label(&category), &count]))
+        })
+        .collect();
+
+    tf(Msg::JiTruncated, &[&omitted.len(), &counts.join(t(Msg::JiTruncatedSeparator))])
+}
+
+/// 在预算内生成召回总览，截断时附加说明
+pub fn summarize_within_budget(memories: Vec<MemoryEntry>, categories: &CategoryRegistry, budget: RecallBudget) -> String {
+    let (included, omitted) = fill(memories, categories, budget);
+    if included.is_empty() && !omitted.is_empty() {
+        return truncation_note(&omitted, categories);
+    }
+
+    let summary = summarize_memories(&included, categories);
+    if omitted.is_empty() {
+        summary
+    } else {
+        format!("{}\n{}", summary, truncation_note(&omitted, categories))
+    }
+}
+
+/// 按分类召回优先级排序，同一分类内保持原有顺序（更新时间或相关度）
+pub fn order_by_priority(memories: &mut [MemoryEntry], categories: &CategoryRegistry) {
+    let order: Vec<MemoryCategory> = categories.all().collect();
+    memories.sort_by_key(|m| order.iter().position(|c| *c == m.category).unwrap_or(order.len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::entry;
+
+    #[test]
+    fn test_fill_keeps_rules_and_reports_omitted() {
+        let categories = CategoryRegistry::builtin();
+        let mut memories = vec![
+            entry("Project started in 2023 as an internal tool").category(MemoryCategory::CONTEXT).build(),
+            entry("Use pnpm").category(MemoryCategory::RULE).build(),
+            entry("Never commit secrets").category(MemoryCategory::RULE).build(),
+        ];
+        order_by_priority(&mut memories, &categories);
+
+        let output = summarize_within_budget(memories, &categories, RecallBudget::Chars(60));
+        assert!(output.starts_with("📚 项目记忆总览: **规范**: Use pnpm; Never commit secrets"), "{}", output);
+        assert!(output.contains("另有 1 条记忆") && output.contains("背景 1 条"), "{}", output);
+        assert!(!output.contains("internal tool"));
+    }
+
+    #[test]
+    fn test_resolve_and_estimate() {
+        assert_eq!(RecallBudget::resolve(None, None, 2000), RecallBudget::Tokens(2000));
+        assert_eq!(RecallBudget::resolve(Some(500), None, 2000), RecallBudget::Chars(500));
+        assert_eq!(RecallBudget::resolve(None, Some(0), 2000), RecallBudget::Unlimited);
+        assert_eq!(estimate_tokens("使用 pnpm"), 2 + 2);
+    }
+}
@@ -0,0 +1,229 @@
+//! 记忆分类注册表
+//!
+//! 内置四个分类，配置中的 `memory_config.categories` 和记忆目录下的 `categories.json` 可以新增分类，
+//! 也可以按标识覆盖已有分类的标题、文件名和召回优先级。后加载的定义优先
+
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use super::layout::METADATA_FILE;
+use super::organize::ORGANIZE_REPORT_FILE;
+use super::types::MemoryCategory;
+use crate::config::{load_standalone_config, MemoryCategoryDef};
+use crate::i18n::{t, Msg};
+use crate::log_important;
+
+/// 项目自定义分类文件名
+pub const PROJECT_CATEGORIES_FILE: &str = "categories.json";
+
+/// 不能用作分类文件名的保留文件
+const RESERVED_FILENAMES: &[&str] = &[METADATA_FILE, PROJECT_CATEGORIES_FILE, ORGANIZE_REPORT_FILE];
+
+/// 分类注册表，按召回优先级从高到低排列
+#[derive(Debug, Clone)]
+pub struct CategoryRegistry {
+    defs: Vec<MemoryCategoryDef>,
+}
+
+fn builtin(key: &str, title: Msg, filename: &str, priority: i32, label: Msg) -> MemoryCategoryDef {
+    MemoryCategoryDef {
+        key: key.to_string(),
+        title: t(title).to_string(),
+        filename: filename.to_string(),
+        priority,
+        label: t(label).to_string(),
+    }
+}
+
+impl CategoryRegistry {
+    /// 只包含内置分类的注册表
+    pub fn builtin() -> Self {
+        Self {
+            defs: vec![
+                builtin("rule", Msg::JiCategoryRuleTitle, "rules.md", 40, Msg::JiCategoryRuleLabel),
+                builtin("preference", Msg::JiCategoryPreferenceTitle, "preferences.md", 30, Msg::JiCategoryPreferenceLabel),
+                builtin("pattern", Msg::JiCategoryPatternTitle, "patterns.md", 20, Msg::JiCategoryPatternLabel),
+                builtin("context", Msg::JiCategoryContextTitle, "context.md", 10, Msg::JiCategoryContextLabel),
+            ],
+        }
+    }
+
+    /// 加载内置分类、配置中的分类以及记忆目录下声明的分类
+    pub fn load(memory_dir: &Path) -> Self {
+        let mut registry = Self::builtin();
+
+        registry.extend(user_categories());
+
+        let project_file = memory_dir.join(PROJECT_CATEGORIES_FILE);
+        if project_file.exists() {
+            match fs::read_to_string(&project_file)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<Vec<MemoryCategoryDef>>(&content)?))
+            {
+                Ok(defs) => registry.extend(defs),
+                Err(e) => log_important!(warn, "读取项目记忆分类失败: {}: {}", project_file.display(), e),
+            }
+        }
+
+        registry
+    }
+
+    /// 新增或覆盖分类定义，无效定义会被忽略
+    pub fn extend(&mut self, defs: impl IntoIterator<Item = MemoryCategoryDef>) {
+        for mut def in defs {
+            def.key = def.key.trim().to_lowercase();
+            if let Err(e) = self.validate(&def) {
+                log_important!(warn, "忽略无效的记忆分类 {}: {}", def.key, e);
+                continue;
+            }
+            match self.defs.iter_mut().find(|existing| existing.key == def.key) {
+                Some(existing) => *existing = def,
+                None => self.defs.push(def),
+            }
+        }
+        self.defs.sort_by_key(|def| std::cmp::Reverse(def.priority));
+    }
+
+    /// 合并另一个注册表中尚未定义的分类
+    pub fn merge_missing(&mut self, other: &CategoryRegistry) {
+        let missing: Vec<MemoryCategoryDef> = other
+            .defs
+            .iter()
+            .filter(|def| self.get_def(&def.key).is_none())
+            .cloned()
+            .collect();
+        self.extend(missing);
+    }
+
+    /// 校验分类定义
+    pub fn validate(&self, def: &MemoryCategoryDef) -> Result<()> {
+        validate_category_def(def)?;
+        if let Some(other) = self
+            .defs
+            .iter()
+            .find(|existing| existing.key != def.key && existing.filename.eq_ignore_ascii_case(&def.filename))
+        {
+            return Err(anyhow::anyhow!("文件名 {} 已被分类 {} 使用", def.filename, other.key));
+        }
+        Ok(())
+    }
+
+    /// 按召回优先级排列的分类定义
+    pub fn defs(&self) -> &[MemoryCategoryDef] {
+        &self.defs
+    }
+
+    /// 按召回优先级排列的所有分类
+    pub fn all(&self) -> impl Iterator<Item = MemoryCategory> + '_ {
+        self.defs.iter().map(|def| MemoryCategory::new(&def.key))
+    }
+
+    /// 所有分类标识
+    pub fn keys(&self) -> Vec<&str> {
+        self.defs.iter().map(|def| def.key.as_str()).collect()
+    }
+
+    /// 根据标识解析分类，忽略大小写
+    pub fn resolve(&self, key: &str) -> Option<MemoryCategory> {
+        self.get_def(key).map(|def| MemoryCategory::new(&def.key))
+    }
+
+    /// 是否已定义该分类
+    pub fn contains(&self, category: &MemoryCategory) -> bool {
+        self.get_def(category.key()).is_some()
+    }
+
+    fn get_def(&self, key: &str) -> Option<&MemoryCategoryDef> {
+        let key = key.trim();
+        self.defs.iter().find(|def| def.key.eq_ignore_ascii_case(key))
+    }
+
+    /// 分类对应的记忆文件名
+    pub fn filename(&self, category: &MemoryCategory) -> String {
+        self.get_def(category.key())
+            .map(|def| def.filename.clone())
+            .unwrap_or_else(|| format!("{}.md", category.key()))
+    }
+
+    /// 分类文件标题
+    pub fn title(&self, category: &MemoryCategory) -> String {
+        self.get_def(category.key())
+            .map(|def| def.title.clone())
+            .unwrap_or_else(|| category.key().to_string())
+    }
+
+    /// 召回总览中的简称
+    pub fn label(&self, category: &MemoryCategory) -> String {
+        match self.get_def(category.key()) {
+            Some(def) if !def.label.trim().is_empty() => def.label.clone(),
+            Some(def) => def.title.clone(),
+            None => category.key().to_string(),
+        }
+    }
+}
+
+/// 用户配置中声明的分类，测试中不读取本机配置，保证结果与运行环境无关
+fn user_categories() -> Vec<MemoryCategoryDef> {
+    if cfg!(test) {
+        return Vec::new();
+    }
+    load_standalone_config()
+        .map(|config| config.memory_config.categories)
+        .unwrap_or_default()
+}
+
+/// 校验单个分类定义的格式
+pub fn validate_category_def(def: &MemoryCategoryDef) -> Result<()> {
+    let key = def.key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow::anyhow!("分类标识只能包含字母、数字、- 和 _: {}", def.key));
+    }
+    if def.title.trim().is_empty() {
+        return Err(anyhow::anyhow!("分类 {} 缺少标题", key));
+    }
+
+    let filename = def.filename.trim();
+    let valid_filename = filename.ends_with(".md")
+        && filename.len() > 3
+        && filename.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !filename.starts_with('.');
+    if !valid_filename || RESERVED_FILENAMES.contains(&filename) {
+        return Err(anyhow::anyhow!("分类 {} 的文件名无效: {}，应为不含路径的 .md 文件名", key, def.filename));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(key: &str, filename: &str, priority: i32) -> MemoryCategoryDef {
+        MemoryCategoryDef {
+            key: key.to_string(),
+            title: key.to_string(),
+            filename: filename.to_string(),
+            priority,
+            label: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_custom_categories_override_and_order() {
+        let mut registry = CategoryRegistry::builtin();
+        registry.extend(vec![
+            def("security", "security.md", 50),
+            def("API-Contract", "api-contract.md", 5),
+            def("context", "context.md", 45),
+            def("bad", "../escape.md", 0),
+            def("clash", "rules.md", 0),
+        ]);
+
+        assert_eq!(registry.keys(), vec!["security", "context", "rule", "preference", "pattern", "api-contract"]);
+        assert_eq!(registry.resolve("Security"), Some(MemoryCategory::new("security")));
+        assert_eq!(registry.filename(&MemoryCategory::new("api-contract")), "api-contract.md");
+        assert_eq!(registry.label(&MemoryCategory::RULE), "规范");
+        assert!(registry.resolve("bad").is_none());
+        assert!(registry.resolve("clash").is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! 记忆去重与冲突检测
+//!
+//! 相似度使用归一化文本（小写、去除空白和标点）上字符二元组的 Dice 系数，对中英文都适用。
+//! 冲突检测是启发式的：同组互斥词各占一边（如 tabs/spaces），或一条是另一条的否定形式
+
+use std::collections::HashMap;
+
+use super::types::MemoryEntry;
+
+/// 相似度不低于该值视为重复
+pub const DUPLICATE_THRESHOLD: f64 = 0.8;
+
+/// 互斥词出现时，其余部分相似度不低于该值才视为冲突，避免无关条目误报
+const CONFLICT_CONTEXT_THRESHOLD: f64 = 0.3;
+
+/// 否定形式与肯定形式的相似度阈值
+const NEGATION_THRESHOLD: f64 = 0.6;
+
+/// 互斥词组，同组内不同词语出现在两条记忆中时视为可能冲突
+const EXCLUSIVE_TERMS: &[&[&str]] = &[
+    &["tabs", "spaces"],
+    &["tab", "space"],
+    &["制表符", "空格"],
+    &["single quotes", "double quotes"],
+    &["单引号", "双引号"],
+    &["npm", "pnpm", "yarn", "bun"],
+    &["camelcase", "snake_case", "kebab-case", "pascalcase"],
+    &["驼峰", "下划线"],
+    &["semicolons", "no semicolons"],
+    &["中文", "英文"],
+];
+
+/// 否定词
+const NEGATION_TERMS: &[&str] = &[
+    "don't", "do not", "never", "avoid", "no longer", "stop using",
+    "不要", "不用", "禁止", "避免", "不再", "不使用", "不允许",
+];
+
+/// 相似记忆
+#[derive(Debug, Clone)]
+pub struct SimilarMemory {
+    pub entry: MemoryEntry,
+    pub similarity: f64,
+}
+
+/// 归一化文本：小写并去除空白和标点
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 计算两段文本的相似度，范围 0.0 ~ 1.0
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a = normalize(a);
+    let b = normalize(b);
+    if a == b {
+        return 1.0;
+    }
+
+    let a_grams = bigrams(&a);
+    let b_grams = bigrams(&b);
+    let total = a_grams.values().sum::<usize>() + b_grams.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let shared: usize = a_grams
+        .iter()
+        .map(|(gram, count)| (*count).min(b_grams.get(gram).copied().unwrap_or(0)))
+        .sum();
+    2.0 * shared as f64 / total as f64
+}
+
+fn bigrams(text: &str) -> HashMap<(char, char), usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut grams = HashMap::new();
+    if chars.len() == 1 {
+        grams.insert((chars[0], chars[0]), 1);
+    }
+    for pair in chars.windows(2) {
+        *grams.entry((pair[0], pair[1])).or_insert(0) += 1;
+    }
+    grams
+}
+
+/// 查找与内容最相似且达到重复阈值的记忆
+///
+/// 相互矛盾或数字不同的条目（如 "Node version 18" 与 "Node version 20"）文字再相似也不算重复，
+/// 否则合并时只会留下其中一个取值
+pub fn find_duplicate(content: &str, existing: &[MemoryEntry]) -> Option<SimilarMemory> {
+    let content_numbers = numbers(content);
+    existing
+        .iter()
+        .map(|entry| (entry, similarity(content, &entry.content)))
+        .filter(|(_, similarity)| *similarity >= DUPLICATE_THRESHOLD)
+        .filter(|(entry, _)| numbers(&entry.content) == content_numbers && !is_conflict(content, &entry.content))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entry, similarity)| SimilarMemory { entry: entry.clone(), similarity })
+}
+
+/// 文本中依次出现的数字
+fn numbers(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_ascii_digit()).filter(|part| !part.is_empty()).collect()
+}
+
+/// 查找可能与内容相互矛盾的记忆
+pub fn find_conflicts(content: &str, existing: &[MemoryEntry]) -> Vec<MemoryEntry> {
+    existing
+        .iter()
+        .filter(|entry| is_conflict(content, &entry.content))
+        .cloned()
+        .collect()
+}
+
+/// 合并两条相似记忆的内容，保留信息更完整的一条
+pub fn merge_content(existing: &str, incoming: &str) -> String {
+    if normalize(incoming).chars().count() >= normalize(existing).chars().count() {
+        incoming.trim().to_string()
+    } else {
+        existing.trim().to_string()
+    }
+}
+
+fn is_conflict(a: &str, b: &str) -> bool {
+    let a_lower = a.to_lowercase();
+    let b_lower = b.to_lowercase();
+
+    for group in EXCLUSIVE_TERMS {
+        let a_terms: Vec<&str> = group.iter().copied().filter(|t| contains_term(&a_lower, t)).collect();
+        let b_terms: Vec<&str> = group.iter().copied().filter(|t| contains_term(&b_lower, t)).collect();
+        let opposed = a_terms.iter().any(|t| !b_terms.contains(t)) && b_terms.iter().any(|t| !a_terms.contains(t));
+        if !a_terms.is_empty() && !b_terms.is_empty() && opposed {
+            let a_rest = strip_terms(&a_lower, group);
+            let b_rest = strip_terms(&b_lower, group);
+            if similarity(&a_rest, &b_rest) >= CONFLICT_CONTEXT_THRESHOLD {
+                return true;
+            }
+        }
+    }
+
+    let a_negated = NEGATION_TERMS.iter().any(|t| a_lower.contains(t));
+    let b_negated = NEGATION_TERMS.iter().any(|t| b_lower.contains(t));
+    if a_negated != b_negated {
+        let a_rest = strip_terms(&a_lower, NEGATION_TERMS);
+        let b_rest = strip_terms(&b_lower, NEGATION_TERMS);
+        return similarity(&a_rest, &b_rest) >= NEGATION_THRESHOLD;
+    }
+
+    false
+}
+
+/// 判断是否包含词语，英文词按单词边界匹配
+fn contains_term(text: &str, term: &str) -> bool {
+    if !term.is_ascii() {
+        return text.contains(term);
+    }
+    text.match_indices(term).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + term.len()..].chars().next();
+        !before.is_some_and(|c| c.is_ascii_alphanumeric())
+            && !after.is_some_and(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+fn strip_terms(text: &str, terms: &[&str]) -> String {
+    let mut sorted: Vec<&str> = terms.to_vec();
+    // 先去除较长的词，避免 "no semicolons" 被 "semicolons" 截断
+    sorted.sort_by_key(|t| std::cmp::Reverse(t.len()));
+    sorted.iter().fold(text.to_string(), |acc, term| acc.replace(term, " "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::entry;
+
+    #[test]
+    fn test_find_duplicate_with_different_wording() {
+        let existing = vec![entry("Use pnpm to install dependencies.").build(), entry("提交前运行测试").build()];
+
+        let duplicate = find_duplicate("use pnpm to install dependencies", &existing).unwrap();
+        assert_eq!(duplicate.entry.content, "Use pnpm to install dependencies.");
+        assert!(find_duplicate("提交前 运行测试！", &existing).is_some());
+        assert!(find_duplicate("数据库迁移前先备份", &existing).is_none());
+    }
+
+    #[test]
+    fn test_contradictions_are_not_duplicates() {
+        for (existing, incoming) in [
+            ("提交信息使用中文", "不要提交信息使用中文"),
+            ("Use semicolons", "Don't use semicolons"),
+            ("Node version 18", "Node version 20"),
+            ("API 端口 8080", "API 端口 8081"),
+        ] {
+            assert!(similarity(existing, incoming) >= DUPLICATE_THRESHOLD, "{}", incoming);
+            assert!(find_duplicate(incoming, &[entry(existing).build()]).is_none(), "{}", incoming);
+        }
+        assert!(find_duplicate("Node version 18.", &[entry("node version 18").build()]).is_some());
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let existing = vec![
+            entry("Indent with tabs").build(),
+            entry("提交信息使用中文").build(),
+            entry("Use yarn for scripts").build(),
+        ];
+
+        assert_eq!(find_conflicts("Indent with spaces", &existing).len(), 1);
+        assert_eq!(find_conflicts("不要提交信息使用中文", &existing).len(), 1);
+        assert_eq!(find_conflicts("Use pnpm for scripts", &existing)[0].content, "Use yarn for scripts");
+        assert!(find_conflicts("数据库迁移前先备份", &existing).is_empty());
+    }
+}
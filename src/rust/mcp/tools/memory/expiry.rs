@@ -0,0 +1,137 @@
+//! 记忆过期时间
+//!
+//! “正在排查 issue 123”这类上下文只在一段时间内有意义。条目可以带过期时间，过期后不再出现在
+//! 回忆和检索结果中，整理时删除。未指定时使用配置中该分类的默认有效期
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
+
+use super::types::MemoryCategory;
+use crate::i18n::{t, tf, Msg};
+
+/// 添加记忆时的过期设置
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Expiry {
+    /// 使用分类的默认有效期，未配置时永不过期
+    #[default]
+    Default,
+    /// 永不过期
+    Never,
+    /// 在指定时间过期
+    At(DateTime<Utc>),
+}
+
+impl Expiry {
+    /// 解析 ttl 和 expires_at 参数，都未提供时返回 None
+    pub fn from_params(ttl: Option<&str>, expires_at: Option<&str>, now: DateTime<Utc>) -> Result<Option<Self>> {
+        let ttl = ttl.map(str::trim).filter(|t| !t.is_empty());
+        let expires_at = expires_at.map(str::trim).filter(|t| !t.is_empty());
+        match (ttl, expires_at) {
+            (None, None) => Ok(None),
+            (Some(_), Some(_)) => Err(anyhow::anyhow!(t(Msg::JiErrTtlConflict))),
+            (Some(ttl), None) => Ok(Some(match parse_ttl(ttl)? {
+                Some(duration) => Expiry::At(
+                    now.checked_add_signed(duration)
+                        .ok_or_else(|| anyhow::anyhow!(tf(Msg::JiErrTtlTooLong, &[&ttl])))?,
+                ),
+                None => Expiry::Never,
+            })),
+            (None, Some(expires_at)) => {
+                let at = parse_expires_at(expires_at)?;
+                if at <= now {
+                    return Err(anyhow::anyhow!(tf(Msg::JiErrExpiryPast, &[&expires_at])));
+                }
+                Ok(Some(Expiry::At(at)))
+            }
+        }
+    }
+
+    /// 确定条目的过期时间，配置的有效期长到无法表示时视为永不过期
+    pub fn resolve(
+        self,
+        ttl_days: &HashMap<String, u32>,
+        category: &MemoryCategory,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        match self {
+            Expiry::Default => ttl_days
+                .get(category.key())
+                .filter(|&&days| days > 0)
+                .and_then(|&days| Duration::try_days(days as i64).and_then(|ttl| now.checked_add_signed(ttl))),
+            Expiry::Never => None,
+            Expiry::At(at) => Some(at),
+        }
+    }
+}
+
+/// 解析有效期，如 "30m"、"12h"、"7d"、"2w"，不带单位时按天计算。"0" 或 "never" 表示永不过期
+pub fn parse_ttl(ttl: &str) -> Result<Option<Duration>> {
+    let ttl = ttl.trim().to_lowercase();
+    if ttl == "never" || ttl == "0" {
+        return Ok(None);
+    }
+
+    let split = ttl.find(|c: char| !c.is_ascii_digit()).unwrap_or(ttl.len());
+    let (amount, unit) = ttl.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| anyhow::anyhow!(tf(Msg::JiErrTtlInvalid, &[&ttl])))?;
+    let duration = match unit.trim() {
+        "m" | "min" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "" | "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => return Err(anyhow::anyhow!(tf(Msg::JiErrTtlUnit, &[&unit.trim()]))),
+    }
+    .ok_or_else(|| anyhow::anyhow!(tf(Msg::JiErrTtlTooLong, &[&ttl])))?;
+    Ok((amount > 0).then_some(duration))
+}
+
+/// 解析过期时间，支持 RFC 3339 时间和 YYYY-MM-DD 日期，日期按 UTC 当天结束时过期
+pub fn parse_expires_at(value: &str) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.succ_opt())
+        .and_then(|next| next.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc())
+        .ok_or_else(|| anyhow::anyhow!(tf(Msg::JiErrExpiresAtInvalid, &[&value])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_params_and_category_default() {
+        let now = Utc::now();
+        assert_eq!(parse_ttl("7d").unwrap(), Some(Duration::days(7)));
+        assert_eq!(parse_ttl("12H").unwrap(), Some(Duration::hours(12)));
+        assert_eq!(parse_ttl("never").unwrap(), None);
+        assert!(parse_ttl("soon").is_err());
+        assert!(parse_ttl("9999999999999999d").is_err());
+        assert!(Expiry::from_params(Some("100000000d"), None, now).is_err());
+
+        assert_eq!(
+            parse_expires_at("2030-01-31").unwrap().to_rfc3339(),
+            "2030-02-01T00:00:00+00:00"
+        );
+        assert!(Expiry::from_params(None, Some("2000-01-01"), now).is_err());
+        assert!(Expiry::from_params(Some("1d"), Some("2030-01-01"), now).is_err());
+        assert_eq!(Expiry::from_params(Some("0"), None, now).unwrap(), Some(Expiry::Never));
+
+        let defaults = HashMap::from([("context".to_string(), 30)]);
+        assert_eq!(
+            Expiry::Default.resolve(&defaults, &MemoryCategory::CONTEXT, now),
+            Some(now + Duration::days(30))
+        );
+        assert_eq!(Expiry::Default.resolve(&defaults, &MemoryCategory::RULE, now), None);
+        assert_eq!(Expiry::Never.resolve(&defaults, &MemoryCategory::CONTEXT, now), None);
+        let huge = HashMap::from([("context".to_string(), u32::MAX)]);
+        assert_eq!(Expiry::Default.resolve(&huge, &MemoryCategory::CONTEXT, now), None);
+    }
+}
@@ -0,0 +1,335 @@
+//! 记忆文件格式
+//!
+//! 每条记忆是一个 Markdown 列表项，元数据以 HTML 注释附在条目末尾，渲染时不可见：
+//!
+//! ```text
+//! - 使用 pnpm 管理依赖 <!-- memory:{"id":"…","created_at":"…","updated_at":"…"} -->
+//! ```
+//!
+//! 多行内容以两个空格缩进续行。没有元数据的旧格式条目会在读取时补全并标记为需要迁移。
+//! 无法识别为记忆的手写内容在改写文件时移到文件末尾的保留区，不会被丢弃
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::types::{MemoryCategory, MemoryEntry, MemoryScope};
+
+/// 当前记忆文件格式版本
+pub const FORMAT_VERSION: &str = "2.0.0";
+
+const META_PREFIX: &str = "<!-- memory:";
+const META_SUFFIX: &str = "-->";
+
+/// 保留区标记，之后的内容原样保留，不解析为记忆
+const PRESERVED_MARKER: &str = "<!-- memory:preserved -->";
+
+/// 条目元数据（序列化到HTML注释中）
+#[derive(Serialize, Deserialize)]
+struct EntryMeta {
+    id: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// 解析结果
+pub struct ParsedFile {
+    pub entries: Vec<MemoryEntry>,
+    /// 是否存在缺少元数据的旧格式条目，或ID无效、重复而被重新生成的条目
+    pub needs_migration: bool,
+    /// 无法识别为记忆的行（标题和空行除外），改写文件时原样保留
+    pub preserved: Vec<String>,
+}
+
+/// 记忆ID只能包含字母、数字、- 和 _，按条目存放时ID用作文件名
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// 为文件中ID重复的条目重新生成ID，`seen` 记录已出现的ID，返回是否有条目被重新生成ID
+///
+/// 合并分支或复制粘贴后可能出现相同ID的条目，按ID写入或修改时会互相覆盖
+pub fn reassign_duplicate_ids(entries: &mut [MemoryEntry], seen: &mut HashSet<String>) -> bool {
+    let mut reassigned = false;
+    for entry in entries {
+        if !seen.insert(entry.id.clone()) {
+            entry.id = uuid::Uuid::new_v4().to_string();
+            seen.insert(entry.id.clone());
+            reassigned = true;
+        }
+    }
+    reassigned
+}
+
+/// 解析记忆文件
+///
+/// 旧格式条目以及ID无效或重复的条目使用新生成的ID，旧格式条目以 `now` 作为时间戳，
+/// 调用方应把结果写回文件以固定ID。与旧版一致，去掉缩进后以 `- ` 开头的行也视为条目
+pub fn parse_entries(content: &str, category: &MemoryCategory, now: DateTime<Utc>) -> ParsedFile {
+    let mut entries = Vec::new();
+    let mut needs_migration = false;
+    let mut preserved = Vec::new();
+    let mut title_seen = false;
+    let mut current: Option<Vec<&str>> = None;
+
+    let mut finish = |lines: Vec<&str>, entries: &mut Vec<MemoryEntry>| {
+        if let Some(entry) = build_entry(&lines, category, now) {
+            if entry.1 {
+                needs_migration = true;
+            }
+            entries.push(entry.0);
+        }
+    };
+
+    let all_lines: Vec<&str> = content.lines().collect();
+    for (index, line) in all_lines.iter().copied().enumerate() {
+        if line.trim() == PRESERVED_MARKER {
+            preserved.extend(all_lines[index + 1..].iter().map(|line| line.to_string()));
+            break;
+        }
+        if let Some(lines) = current.as_mut() {
+            if let Some(rest) = line.strip_prefix("  ") {
+                lines.push(rest);
+                continue;
+            }
+            if line.trim().is_empty() && continues_entry(&all_lines[index + 1..]) {
+                // 编辑器会去掉空行的缩进，后面仍有缩进的续行时空行属于当前条目
+                lines.push("");
+                continue;
+            }
+        }
+        if let Some(lines) = current.take() {
+            finish(lines, &mut entries);
+        }
+
+        if let Some(rest) = line.trim_start().strip_prefix("- ") {
+            current = Some(vec![rest]);
+        } else if !title_seen && line.starts_with("# ") {
+            title_seen = true;
+        } else if !line.trim().is_empty() {
+            preserved.push(line.to_string());
+        }
+    }
+    if let Some(lines) = current.take() {
+        finish(lines, &mut entries);
+    }
+    if reassign_duplicate_ids(&mut entries, &mut HashSet::new()) {
+        needs_migration = true;
+    }
+
+    // 保留区末尾的空行来自写入时的格式，不属于手写内容
+    while preserved.last().is_some_and(|line: &String| line.trim().is_empty()) {
+        preserved.pop();
+    }
+
+    ParsedFile { entries, needs_migration, preserved }
+}
+
+/// 空行之后的第一个非空行是否为缩进的续行
+fn continues_entry(rest: &[&str]) -> bool {
+    rest.iter()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.starts_with("  "))
+}
+
+/// 由列表项的各行构建条目，返回 (条目, 是否需要写回)
+fn build_entry(lines: &[&str], category: &MemoryCategory, now: DateTime<Utc>) -> Option<(MemoryEntry, bool)> {
+    let mut lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    let mut meta = None;
+
+    if let Some(last) = lines.last_mut() {
+        let trimmed = last.trim_end();
+        if trimmed.ends_with(META_SUFFIX) {
+            if let Some(start) = trimmed.rfind(META_PREFIX) {
+                let json = &trimmed[start + META_PREFIX.len()..trimmed.len() - META_SUFFIX.len()];
+                meta = serde_json::from_str::<EntryMeta>(json.trim()).ok();
+                if meta.is_some() {
+                    *last = trimmed[..start].trim_end().to_string();
+                }
+            }
+        }
+    }
+
+    let content = lines.join("\n").trim().to_string();
+    if content.is_empty() {
+        return None;
+    }
+
+    let mut needs_migration = meta.is_none();
+    let mut meta = meta.unwrap_or_else(|| EntryMeta {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: now,
+        updated_at: now,
+        tags: Vec::new(),
+        branch: None,
+        expires_at: None,
+    });
+    // ID来自仓库中的文件，可能被手工改成路径等无效值
+    if !is_valid_id(&meta.id) {
+        meta.id = uuid::Uuid::new_v4().to_string();
+        needs_migration = true;
+    }
+
+    Some((
+        MemoryEntry {
+            id: meta.id,
+            content,
+            category: category.clone(),
+            created_at: meta.created_at,
+            updated_at: meta.updated_at,
+            tags: meta.tags,
+            scope: MemoryScope::default(),
+            branch: meta.branch,
+            expires_at: meta.expires_at,
+        },
+        needs_migration,
+    ))
+}
+
+/// 格式化单条记忆
+pub fn format_entry(entry: &MemoryEntry) -> String {
+    let meta = EntryMeta {
+        id: entry.id.clone(),
+        created_at: entry.created_at,
+        updated_at: entry.updated_at,
+        tags: entry.tags.clone(),
+        branch: entry.branch.clone(),
+        expires_at: entry.expires_at,
+    };
+    let meta_json = serde_json::to_string(&meta).unwrap_or_default();
+
+    let mut output = String::new();
+    for (index, line) in entry.content.trim().lines().enumerate() {
+        let line = line.trim_end();
+        if index == 0 {
+            output.push_str("- ");
+        } else if line.is_empty() {
+            // 空行不写缩进，避免留下会被编辑器去掉的行尾空白
+            output.push('\n');
+        } else {
+            output.push_str("\n  ");
+        }
+        output.push_str(line);
+    }
+    output.push_str(&format!(" {}{} {}\n", META_PREFIX, meta_json, META_SUFFIX));
+    output
+}
+
+/// 读取分类文件标题
+pub fn read_title(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+}
+
+/// 格式化整个分类文件
+pub fn format_file(title: &str, entries: &[MemoryEntry]) -> String {
+    format_file_preserving(title, entries, &[])
+}
+
+/// 格式化整个分类文件，无法识别的手写内容写在末尾的保留区
+pub fn format_file_preserving(title: &str, entries: &[MemoryEntry], preserved: &[String]) -> String {
+    let mut output = format!("# {}\n\n", title);
+    for entry in entries {
+        output.push_str(&format_entry(entry));
+    }
+    if !preserved.is_empty() {
+        output.push_str(&format!("\n{}\n", PRESERVED_MARKER));
+        for line in preserved {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::entry;
+
+    #[test]
+    fn test_legacy_entries_get_stable_ids_after_round_trip() {
+        let now = Utc::now();
+        let legacy = "# 开发规范和规则\n\n- 使用 pnpm\n- 提交前运行测试\n";
+
+        let parsed = parse_entries(legacy, &MemoryCategory::RULE, now);
+        assert!(parsed.needs_migration);
+        assert_eq!(parsed.entries.len(), 2);
+
+        let written = format_file("开发规范和规则", &parsed.entries);
+        assert_eq!(read_title(&written).as_deref(), Some("开发规范和规则"));
+        let reparsed = parse_entries(&written, &MemoryCategory::RULE, Utc::now());
+        assert!(!reparsed.needs_migration);
+        assert_eq!(reparsed.entries[0].id, parsed.entries[0].id);
+        assert_eq!(reparsed.entries[1].content, "提交前运行测试");
+        assert_eq!(reparsed.entries[1].created_at, now);
+    }
+
+    #[test]
+    fn test_multiline_content_and_metadata_round_trip() {
+        let now = Utc::now();
+        let pattern = entry("接口返回统一结构\n错误码见 docs/errors.md")
+            .id("m1")
+            .category(MemoryCategory::PATTERN)
+            .at(now)
+            .tags(&["api"])
+            .branch("feature/x")
+            .expires_at(now + chrono::Duration::days(7))
+            .build();
+
+        let parsed = parse_entries(&format_file("常用模式和最佳实践", &[pattern]), &MemoryCategory::PATTERN, now);
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.entries[0].content, "接口返回统一结构\n错误码见 docs/errors.md");
+        assert_eq!(parsed.entries[0].tags, vec!["api".to_string()]);
+        assert_eq!(parsed.entries[0].branch.as_deref(), Some("feature/x"));
+        assert_eq!(parsed.entries[0].expires_at, Some(now + chrono::Duration::days(7)));
+    }
+
+    #[test]
+    fn test_blank_lines_in_content_survive_editor_whitespace_stripping() {
+        let now = Utc::now();
+        let first = entry("发布步骤：\n\n1. 打标签\n\n\n2. 推送").id("m1").category(MemoryCategory::PATTERN).at(now).build();
+        let second = entry("第二条").id("m2").category(MemoryCategory::PATTERN).at(now).build();
+
+        let written = format_file("常用模式和最佳实践", &[first, second]);
+        assert!(!written.lines().any(|line| line != line.trim_end()));
+
+        // 模拟编辑器去掉行尾空白后仍能完整读回
+        let stripped: String = written.lines().map(|line| format!("{}\n", line.trim_end())).collect();
+        let parsed = parse_entries(&stripped, &MemoryCategory::PATTERN, now);
+        assert!(!parsed.needs_migration);
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[0].content, "发布步骤：\n\n1. 打标签\n\n\n2. 推送");
+        assert_eq!(parsed.entries[1].content, "第二条");
+
+        let legacy = parse_entries("- 第一行\n  \n  第二行 <!-- memory: {\"id\":\"m3\",\"created_at\":\"2024-01-01T00:00:00Z\",\"updated_at\":\"2024-01-01T00:00:00Z\"} -->\n\n- 下一条\n", &MemoryCategory::PATTERN, now);
+        assert_eq!(legacy.entries[0].content, "第一行\n\n第二行");
+        assert_eq!(legacy.entries[1].content, "下一条");
+    }
+
+    #[test]
+    fn test_unrecognized_lines_are_preserved() {
+        let now = Utc::now();
+        let legacy = "# 开发规范和规则\n\n团队约定如下：\n\n## 前端\n  - 使用 pnpm\n* 组件使用 PascalCase\n- 提交前运行测试\n";
+
+        let parsed = parse_entries(legacy, &MemoryCategory::RULE, now);
+        let contents: Vec<&str> = parsed.entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, ["使用 pnpm", "提交前运行测试"]);
+        assert_eq!(parsed.preserved, ["团队约定如下：", "## 前端", "* 组件使用 PascalCase"]);
+
+        let written = format_file_preserving("开发规范和规则", &parsed.entries, &parsed.preserved);
+        let reparsed = parse_entries(&written, &MemoryCategory::RULE, now);
+        assert!(!reparsed.needs_migration);
+        assert_eq!(reparsed.entries.len(), 2);
+        assert_eq!(reparsed.preserved, parsed.preserved);
+        assert_eq!(format_file_preserving("开发规范和规则", &reparsed.entries, &reparsed.preserved), written);
+    }
+}
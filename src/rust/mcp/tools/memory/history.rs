@@ -0,0 +1,169 @@
+//! 记忆变更历史
+//!
+//! 每次添加、更新、删除都在记忆目录的 history.jsonl 末尾追加一行，保存变更前后的完整条目，
+//! 误操作后可按变更ID撤销。记录只追加不改写，撤销本身也作为一条新的变更记录
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use super::types::{MemoryEntry, MemoryScope};
+use crate::i18n::{t, Msg};
+use crate::log_debug;
+
+/// 历史文件名
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// 查看历史时默认返回的记录数
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// 变更来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSource {
+    /// 通过 ji 工具
+    #[default]
+    Agent,
+    /// 通过设置界面
+    Gui,
+}
+
+impl ChangeSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeSource::Agent => t(Msg::JiSourceAgent),
+            ChangeSource::Gui => t(Msg::JiSourceGui),
+        }
+    }
+}
+
+/// 变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Add,
+    Update,
+    Delete,
+}
+
+impl ChangeKind {
+    /// 由变更前后的条目推断类型
+    fn between(before: Option<&MemoryEntry>, after: Option<&MemoryEntry>) -> Self {
+        match (before, after) {
+            (None, _) => ChangeKind::Add,
+            (Some(_), None) => ChangeKind::Delete,
+            (Some(_), Some(_)) => ChangeKind::Update,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Add => t(Msg::JiKindAdd),
+            ChangeKind::Update => t(Msg::JiKindUpdate),
+            ChangeKind::Delete => t(Msg::JiKindDelete),
+        }
+    }
+}
+
+/// 一次变更记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeRecord {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub source: ChangeSource,
+    pub kind: ChangeKind,
+    pub memory_id: String,
+    /// 变更前的条目，添加时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<MemoryEntry>,
+    /// 变更后的条目，删除时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<MemoryEntry>,
+    /// 该记录撤销的变更ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<String>,
+    /// 变更同时移动了归档目录中的副本：整理时归档条目，或撤销归档时从归档中取回
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// 所属作用域，由存储位置决定
+    #[serde(default)]
+    pub scope: MemoryScope,
+}
+
+impl ChangeRecord {
+    pub fn new(source: ChangeSource, scope: MemoryScope, before: Option<MemoryEntry>, after: Option<MemoryEntry>) -> Self {
+        let memory_id = after.as_ref().or(before.as_ref()).map(|entry| entry.id.clone()).unwrap_or_default();
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string()[..12].to_string(),
+            timestamp: Utc::now(),
+            source,
+            kind: ChangeKind::between(before.as_ref(), after.as_ref()),
+            memory_id,
+            before,
+            after,
+            undoes: None,
+            archived: false,
+            scope,
+        }
+    }
+
+    /// 变更涉及的记忆内容，优先取变更后的内容
+    pub fn content(&self) -> &str {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(|entry| entry.content.as_str())
+            .unwrap_or_default()
+    }
+}
+
+/// 追加一条变更记录，调用方需持有目录锁
+pub fn append(dir: &Path, record: &ChangeRecord) -> Result<()> {
+    let path = dir.join(HISTORY_FILE);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| anyhow::anyhow!("无法打开记忆历史文件: {}\n错误: {}", path.display(), e))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// 读取全部变更记录，按写入顺序排列，无法解析的行会被跳过
+pub fn read_history(dir: &Path, scope: MemoryScope) -> Result<Vec<ChangeRecord>> {
+    let path = dir.join(HISTORY_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut records = Vec::new();
+    for line in fs::read_to_string(&path)?.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<ChangeRecord>(line) {
+            Ok(mut record) => {
+                record.scope = scope;
+                records.push(record);
+            }
+            Err(e) => log_debug!("跳过无法解析的记忆历史记录: {}", e),
+        }
+    }
+    Ok(records)
+}
+
+/// 条目是否仍处于记录时的状态
+pub fn same_revision(a: &MemoryEntry, b: &MemoryEntry) -> bool {
+    a.updated_at == b.updated_at
+        && a.content == b.content
+        && a.category == b.category
+        && a.tags == b.tags
+        && a.branch == b.branch
+        && a.expires_at == b.expires_at
+}
+
+/// 撤销了该变更的记录，未被撤销时为空
+pub fn undone_by<'a>(records: &'a [ChangeRecord], change_id: &str) -> Option<&'a ChangeRecord> {
+    records.iter().find(|record| record.undoes.as_deref() == Some(change_id))
+}
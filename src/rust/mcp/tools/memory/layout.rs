@@ -0,0 +1,439 @@
+//! 记忆目录布局
+//!
+//! 默认每个分类一个 Markdown 文件，metadata.json 中记录条目数和整理时间。团队提交记忆目录时，
+//! 两个分支各自追加条目都会改到文件末尾，每次写入又都会刷新计数，几乎每次合并都会冲突。
+//!
+//! 按条目存放时每条记忆单独写成 `entries/<分类>/<ID>.md`，只有改动过的条目对应的文件会变化。
+//! 此时 metadata.json 只记录格式版本和布局，条目数、整理时间写入不提交的 state.json
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::category::CategoryRegistry;
+use super::format;
+use super::organize::{archive_title, ARCHIVE_DIR_NAME};
+use super::store::atomic_write;
+use super::types::{MemoryCategory, MemoryEntry};
+use crate::i18n::{t, Msg};
+
+/// 元数据文件名
+pub const METADATA_FILE: &str = "metadata.json";
+
+/// 按条目存放时保存易变状态的文件名，不提交到仓库
+pub const STATE_FILE: &str = "state.json";
+
+/// 按条目存放时的条目目录名
+pub const ENTRIES_DIR_NAME: &str = "entries";
+
+/// 记忆目录布局
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryLayout {
+    /// 每个分类一个文件
+    #[default]
+    Category,
+    /// 每条记忆一个文件
+    Entry,
+}
+
+/// 按条目存放时 metadata.json 的内容，只包含不随写入变化的字段
+#[derive(Serialize, Deserialize)]
+struct LayoutMarker {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    layout: Option<MemoryLayout>,
+}
+
+impl MemoryLayout {
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.trim() {
+            "category" => Some(MemoryLayout::Category),
+            "entry" => Some(MemoryLayout::Entry),
+            _ => None,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            MemoryLayout::Category => "category",
+            MemoryLayout::Entry => "entry",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MemoryLayout::Category => t(Msg::JiLayoutCategory),
+            MemoryLayout::Entry => t(Msg::JiLayoutEntry),
+        }
+    }
+
+    /// 判断记忆目录的布局，以 metadata.json 中记录的为准，未记录时看是否存在条目目录
+    pub fn detect(dir: &Path) -> Self {
+        fs::read_to_string(dir.join(METADATA_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<LayoutMarker>(&content).ok())
+            .and_then(|marker| marker.layout)
+            .unwrap_or(if dir.join(ENTRIES_DIR_NAME).is_dir() {
+                MemoryLayout::Entry
+            } else {
+                MemoryLayout::Category
+            })
+    }
+
+    /// 条目数、整理时间等易变状态所在的文件
+    pub fn state_file(&self) -> &'static str {
+        match self {
+            MemoryLayout::Category => METADATA_FILE,
+            MemoryLayout::Entry => STATE_FILE,
+        }
+    }
+
+    /// 写入 metadata.json 的布局标记，按分类存放时元数据与状态写在同一文件中，无需标记
+    pub fn write_marker(&self, dir: &Path) -> Result<()> {
+        if *self == MemoryLayout::Category {
+            return Ok(());
+        }
+        let marker = LayoutMarker {
+            version: Some(format::FORMAT_VERSION.to_string()),
+            layout: Some(*self),
+        };
+        let content = format!("{}\n", serde_json::to_string_pretty(&marker)?);
+        write_if_changed(&dir.join(METADATA_FILE), &content)
+    }
+
+    /// 分类在目录中的位置，用于整理差异的标题
+    pub fn location(&self, category: &MemoryCategory, filename: &str) -> String {
+        match self {
+            MemoryLayout::Category => filename.to_string(),
+            MemoryLayout::Entry => format!("{}/{}/", ENTRIES_DIR_NAME, category.key()),
+        }
+    }
+
+    /// 读取分类下的全部条目，按条目存放时按创建时间排序
+    pub fn read(&self, dir: &Path, category: &MemoryCategory, filename: &str, now: DateTime<Utc>) -> Result<Vec<MemoryEntry>> {
+        Ok(self.parse(dir, category, filename, now)?.entries)
+    }
+
+    /// 读取分类并判断是否需要写回：存在旧格式条目、ID无效或重复，
+    /// 或按条目存放时文件名与ID不一致、一个文件中有多条记忆
+    pub fn parse(&self, dir: &Path, category: &MemoryCategory, filename: &str, now: DateTime<Utc>) -> Result<format::ParsedFile> {
+        match self {
+            MemoryLayout::Category => {
+                let path = dir.join(filename);
+                if !path.exists() {
+                    return Ok(format::ParsedFile { entries: Vec::new(), needs_migration: false, preserved: Vec::new() });
+                }
+                Ok(format::parse_entries(&fs::read_to_string(&path)?, category, now))
+            }
+            MemoryLayout::Entry => {
+                let mut entries = Vec::new();
+                let mut needs_migration = false;
+                let mut preserved = Vec::new();
+                let mut seen = HashSet::new();
+                for path in entry_files(&entry_dir(dir, category))? {
+                    let mut parsed = format::parse_entries(&fs::read_to_string(&path)?, category, now);
+                    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+                    needs_migration |= parsed.needs_migration
+                        || format::reassign_duplicate_ids(&mut parsed.entries, &mut seen)
+                        || !matches!(parsed.entries.as_slice(), [entry] if entry.id == stem);
+                    entries.extend(parsed.entries);
+                    preserved.extend(parsed.preserved);
+                }
+                entries.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+                Ok(format::ParsedFile { entries, needs_migration, preserved })
+            }
+        }
+    }
+
+    /// 分类当前的文本和条目，按条目存放时以分类文件的格式渲染，整理时用于生成差异和计划ID
+    pub fn snapshot(
+        &self,
+        dir: &Path,
+        category: &MemoryCategory,
+        filename: &str,
+        title: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(String, Vec<MemoryEntry>)> {
+        match self {
+            MemoryLayout::Category => {
+                let content = fs::read_to_string(dir.join(filename)).unwrap_or_default();
+                let entries = format::parse_entries(&content, category, now).entries;
+                Ok((content, entries))
+            }
+            MemoryLayout::Entry => {
+                let entries = self.read(dir, category, filename, now)?;
+                Ok((format::format_file(title, &entries), entries))
+            }
+        }
+    }
+
+    /// 以分类文件的格式渲染给定条目，按分类存放时保留文件中无法识别为记忆的手写内容
+    pub fn render(&self, dir: &Path, category: &MemoryCategory, filename: &str, title: &str, entries: &[MemoryEntry]) -> String {
+        let preserved = match self {
+            MemoryLayout::Category => fs::read_to_string(dir.join(filename))
+                .map(|content| format::parse_entries(&content, category, Utc::now()).preserved)
+                .unwrap_or_default(),
+            MemoryLayout::Entry => Vec::new(),
+        };
+        format::format_file_preserving(title, entries, &preserved)
+    }
+
+    /// 用给定条目替换分类下的全部条目，按条目存放时只改写内容有变化的文件
+    pub fn write(
+        &self,
+        dir: &Path,
+        category: &MemoryCategory,
+        filename: &str,
+        title: &str,
+        entries: &[MemoryEntry],
+    ) -> Result<()> {
+        match self {
+            MemoryLayout::Category => {
+                fs::create_dir_all(dir)?;
+                atomic_write(&dir.join(filename), self.render(dir, category, filename, title, entries))
+            }
+            MemoryLayout::Entry => {
+                let entry_dir = entry_dir(dir, category);
+                let mut kept = HashSet::new();
+                for entry in entries {
+                    // 写入前检查全部ID，重复的ID会让后写入的条目覆盖先写入的，多余的文件随后又被删除
+                    if !kept.insert(entry_path(&entry_dir, entry)?) {
+                        return Err(anyhow::anyhow!("分类 {} 中存在重复的记忆ID: {}", category, entry.id));
+                    }
+                }
+                fs::create_dir_all(&entry_dir)?;
+                for entry in entries {
+                    write_if_changed(&entry_path(&entry_dir, entry)?, &format::format_entry(entry))?;
+                }
+                for path in entry_files(&entry_dir)? {
+                    if !kept.contains(&path) {
+                        fs::remove_file(&path)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 添加一条记忆
+    pub fn append(&self, dir: &Path, filename: &str, title: &str, entry: &MemoryEntry) -> Result<()> {
+        match self {
+            MemoryLayout::Category => {
+                let path = dir.join(filename);
+                let mut content = if path.exists() {
+                    fs::read_to_string(&path)?
+                } else {
+                    format::format_file(title, &[])
+                };
+
+                if !content.is_empty() && !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push_str(&format::format_entry(entry));
+
+                atomic_write(&path, content)
+            }
+            MemoryLayout::Entry => {
+                let entry_dir = entry_dir(dir, &entry.category);
+                let path = entry_path(&entry_dir, entry)?;
+                fs::create_dir_all(&entry_dir)?;
+                atomic_write(&path, format::format_entry(entry))
+            }
+        }
+    }
+
+    /// 删除分类下的全部条目
+    fn remove(&self, dir: &Path, category: &MemoryCategory, filename: &str) -> Result<()> {
+        match self {
+            MemoryLayout::Category => {
+                let path = dir.join(filename);
+                if path.exists() {
+                    fs::remove_file(&path)?;
+                }
+            }
+            MemoryLayout::Entry => {
+                let entry_dir = entry_dir(dir, category);
+                if entry_dir.is_dir() {
+                    fs::remove_dir_all(&entry_dir)?;
+                }
+                // 其他分类的条目仍在时保留条目目录
+                let _ = fs::remove_dir(dir.join(ENTRIES_DIR_NAME));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 把目录中的条目及其归档转换为另一种布局，返回转换的条目数。调用方需持有目录锁，
+/// 并在之后按新布局写入元数据
+///
+/// 按分类存放时无法从文件名得知未定义的分类，这些分类文件保留在原位置
+pub fn convert_dir(dir: &Path, categories: &CategoryRegistry, from: MemoryLayout, to: MemoryLayout) -> Result<usize> {
+    if from == to {
+        return Ok(0);
+    }
+
+    let now = Utc::now();
+    let archive_dir = dir.join(ARCHIVE_DIR_NAME);
+    let mut all: Vec<MemoryCategory> = categories.all().collect();
+    if from == MemoryLayout::Entry {
+        for category in entry_categories(dir)?.into_iter().chain(entry_categories(&archive_dir)?) {
+            if !all.contains(&category) {
+                all.push(category);
+            }
+        }
+    }
+
+    let mut converted = 0;
+    for category in all {
+        let filename = categories.filename(&category);
+        let title = categories.title(&category);
+
+        // 先写入新布局再删除旧文件，中途失败时宁可重复也不丢失
+        let entries = from.read(dir, &category, &filename, now)?;
+        to.write(dir, &category, &filename, &title, &entries)?;
+        from.remove(dir, &category, &filename)?;
+        converted += entries.len();
+
+        let archived = from.read(&archive_dir, &category, &filename, now)?;
+        if !archived.is_empty() {
+            to.write(&archive_dir, &category, &filename, &archive_title(&title), &archived)?;
+            from.remove(&archive_dir, &category, &filename)?;
+            converted += archived.len();
+        }
+    }
+    Ok(converted)
+}
+
+/// 分类的条目目录
+fn entry_dir(dir: &Path, category: &MemoryCategory) -> PathBuf {
+    dir.join(ENTRIES_DIR_NAME).join(category.key())
+}
+
+/// 条目文件路径，ID无效时报错，避免写到条目目录之外
+fn entry_path(entry_dir: &Path, entry: &MemoryEntry) -> Result<PathBuf> {
+    if !format::is_valid_id(&entry.id) {
+        return Err(anyhow::anyhow!("无效的记忆ID: {}", entry.id));
+    }
+    Ok(entry_dir.join(format!("{}.md", entry.id)))
+}
+
+/// 条目目录下的所有分类
+fn entry_categories(dir: &Path) -> Result<Vec<MemoryCategory>> {
+    let entries_dir = dir.join(ENTRIES_DIR_NAME);
+    if !entries_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut categories = Vec::new();
+    for dir_entry in fs::read_dir(&entries_dir)? {
+        let path = dir_entry?.path();
+        if let (true, Some(key)) = (path.is_dir(), path.file_name().and_then(|name| name.to_str())) {
+            categories.push(MemoryCategory::new(key));
+        }
+    }
+    categories.sort_by(|a, b| a.key().cmp(b.key()));
+    Ok(categories)
+}
+
+/// 条目目录下的记忆文件，按文件名排序，写入中的临时文件会被跳过
+fn entry_files(entry_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !entry_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for dir_entry in fs::read_dir(entry_dir)? {
+        let path = dir_entry?.path();
+        if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// 内容不同时才写入，避免无关的文件时间变化
+fn write_if_changed(path: &Path, content: &str) -> Result<()> {
+    if fs::read_to_string(path).ok().as_deref() == Some(content) {
+        return Ok(());
+    }
+    atomic_write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{entry, TempDir};
+
+    #[test]
+    fn test_entry_layout_round_trip_and_conversion() {
+        let temp = TempDir::new("memory-layout");
+        let dir = temp.path();
+        fs::create_dir_all(dir).unwrap();
+        let registry = CategoryRegistry::builtin();
+        let now = Utc::now();
+        let entries = vec![
+            entry("提交前运行测试").id("b").at(now).build(),
+            entry("使用 pnpm\n不要用 npm").id("a").at(now - chrono::Duration::days(1)).build(),
+        ];
+
+        MemoryLayout::Category.write(dir, &MemoryCategory::RULE, "rules.md", "开发规范和规则", &entries).unwrap();
+        assert_eq!(MemoryLayout::detect(dir), MemoryLayout::Category);
+
+        assert_eq!(convert_dir(dir, &registry, MemoryLayout::Category, MemoryLayout::Entry).unwrap(), 2);
+        MemoryLayout::Entry.write_marker(dir).unwrap();
+        assert_eq!(MemoryLayout::detect(dir), MemoryLayout::Entry);
+        assert!(!dir.join("rules.md").exists());
+        assert!(dir.join(ENTRIES_DIR_NAME).join("rule").join("a.md").exists());
+
+        // 按创建时间读取，删除条目只删除对应的文件
+        let read = MemoryLayout::Entry.read(dir, &MemoryCategory::RULE, "rules.md", now).unwrap();
+        assert_eq!(read.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(read[0].content, "使用 pnpm\n不要用 npm");
+        MemoryLayout::Entry.write(dir, &MemoryCategory::RULE, "rules.md", "开发规范和规则", &read[1..]).unwrap();
+        assert!(!dir.join(ENTRIES_DIR_NAME).join("rule").join("a.md").exists());
+
+        assert_eq!(convert_dir(dir, &registry, MemoryLayout::Entry, MemoryLayout::Category).unwrap(), 1);
+        assert!(!dir.join(ENTRIES_DIR_NAME).exists());
+        let content = fs::read_to_string(dir.join("rules.md")).unwrap();
+        assert!(content.starts_with("# 开发规范和规则\n") && content.contains("提交前运行测试"));
+    }
+
+    #[test]
+    fn test_invalid_and_duplicate_ids_are_reassigned() {
+        let temp = TempDir::new("memory-ids");
+        let dir = temp.path();
+        let rule_dir = dir.join(ENTRIES_DIR_NAME).join("rule");
+        fs::create_dir_all(&rule_dir).unwrap();
+        let now = Utc::now();
+        let original = entry("使用 pnpm").id("a").at(now).build();
+        fs::write(rule_dir.join("a.md"), format::format_entry(&original)).unwrap();
+        // 合并或复制粘贴后出现的同ID文件，以及手工改成路径的ID
+        fs::write(rule_dir.join("a-copy.md"), format::format_entry(&entry("提交前运行测试").id("a").at(now).build())).unwrap();
+        fs::write(rule_dir.join("evil.md"), format::format_entry(&entry("不要提交密钥").id("../../escaped").at(now).build())).unwrap();
+
+        let parsed = MemoryLayout::Entry.parse(dir, &MemoryCategory::RULE, "rules.md", now).unwrap();
+        assert!(parsed.needs_migration);
+        let mut ids: Vec<&str> = parsed.entries.iter().map(|e| e.id.as_str()).collect();
+        assert!(ids.iter().all(|id| format::is_valid_id(id)));
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+
+        MemoryLayout::Entry.write(dir, &MemoryCategory::RULE, "rules.md", "开发规范和规则", &parsed.entries).unwrap();
+        let reread = MemoryLayout::Entry.parse(dir, &MemoryCategory::RULE, "rules.md", now).unwrap();
+        assert!(!reread.needs_migration);
+        assert_eq!(reread.entries.len(), 3);
+        assert!(!dir.join("escaped.md").exists());
+
+        let bad = entry("x").id("../../escaped").at(now).build();
+        assert!(MemoryLayout::Entry.append(dir, "rules.md", "开发规范和规则", &bad).is_err());
+        let duplicated = [original.clone(), original];
+        assert!(MemoryLayout::Entry.write(dir, &MemoryCategory::RULE, "rules.md", "开发规范和规则", &duplicated).is_err());
+        assert_eq!(MemoryLayout::Entry.read(dir, &MemoryCategory::RULE, "rules.md", now).unwrap().len(), 3);
+    }
+}
@@ -1,8 +1,14 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
 use rmcp::{Error as McpError, model::*};
 
-use super::{MemoryManager, MemoryCategory};
-use crate::mcp::{JiyiRequest, utils::{validate_project_path, project_path_error}};
+use super::agent_rules::{self, AgentRulesFile};
+use super::secrets::{self, SecretFinding};
+use super::{AddOutcome, CategoryRegistry, ChangeRecord, Expiry, DEFAULT_HISTORY_LIMIT, LayeredMemory, MemoryFilter, MemoryManager, MemoryCategory, MemoryEntry, MemoryScope, OrganizeReport, RecallBudget, SearchHit, DEFAULT_SEARCH_LIMIT};
+use crate::config::{default_memory_config, load_standalone_config, MemoryConfig};
+use crate::i18n::{t, tf, Msg};
+use crate::mcp::{JiyiAction, JiyiRequest, utils::{validate_project_path, project_path_error}};
 
 /// 全局记忆管理工具
 ///
@@ -16,47 +22,419 @@ impl MemoryTool {
     ) -> Result<CallToolResult, McpError> {
         // 使用增强的路径验证功能
         if let Err(e) = validate_project_path(&request.project_path) {
-            return Err(project_path_error(format!(
-                "路径验证失败: {}\n原始路径: {}\n请检查路径格式是否正确，特别是 Windows 路径应使用正确的盘符格式（如 C:\\path）",
-                e,
-                request.project_path
-            )).into());
+            return Err(project_path_error(tf(Msg::JiPathInvalid, &[&e, &request.project_path])).into());
         }
 
-        let manager = MemoryManager::new(&request.project_path)
-            .map_err(|e| McpError::internal_error(format!("创建记忆管理器失败: {}", e), None))?;
+        let memory = LayeredMemory::open(&request.project_path)
+            .map_err(|e| McpError::internal_error(tf(Msg::JiOpenFailed, &[&e]), None))?;
+        let scope = request.scope;
+        let filter = MemoryFilter {
+            scope,
+            category: parse_category(&memory, request.category.as_deref())?,
+            tags: request.tags.clone().unwrap_or_default(),
+            all_branches: request.action == JiyiAction::List,
+            include_expired: request.action == JiyiAction::List,
+            keep_overridden: request.action == JiyiAction::List,
+        };
 
-        let result = match request.action.as_str() {
-            "记忆" => {
+        let result = match request.action {
+            JiyiAction::Add => {
                 if request.content.trim().is_empty() {
-                    return Err(McpError::invalid_params("缺少记忆内容".to_string(), None));
+                    return Err(McpError::invalid_params(t(Msg::JiMissingContent).to_string(), None));
+                }
+
+                let category = filter.category.clone().unwrap_or(MemoryCategory::CONTEXT);
+                let branch = resolve_branch(&memory, request.branch.as_deref())?;
+                let expiry = parse_expiry(&request)?.unwrap_or_default();
+
+                let (outcome, secrets) = write_layer(&memory, scope)?
+                    .add_memory_checked(&request.content, category.clone(), filter.tags.clone(), request.on_duplicate, branch.clone(), expiry)
+                    .map_err(|e| McpError::internal_error(tf(Msg::JiAddFailed, &[&e]), None))?;
+
+                format_add_outcome(&outcome, &category, scope.unwrap_or_default(), branch.as_deref()) + &format_secrets(&secrets)
+            }
+            JiyiAction::Recall => {
+                let budget = RecallBudget::resolve(request.max_chars, request.max_tokens, memory_config().recall_max_tokens);
+                let query = request.query.trim();
+                if query.is_empty() {
+                    memory.get_project_info(&filter, budget)
+                } else {
+                    memory.get_project_info_matching(query, &filter, search_limit(request.limit), budget)
+                }
+                .map_err(|e| McpError::internal_error(tf(Msg::JiRecallFailed, &[&e]), None))?
+            }
+            JiyiAction::Search => {
+                let query = request.query.trim();
+                if query.is_empty() {
+                    return Err(McpError::invalid_params(t(Msg::JiMissingQuery).to_string(), None));
+                }
+
+                let hits = memory.search_memories(query, &filter, search_limit(request.limit))
+                    .map_err(|e| McpError::internal_error(tf(Msg::JiSearchFailed, &[&e]), None))?;
+
+                format_search_hits(query, &hits)
+            }
+            JiyiAction::List => {
+                let memories = memory.get_memories(&filter)
+                    .map_err(|e| McpError::internal_error(tf(Msg::JiListFailed, &[&e]), None))?;
+
+                format_memory_list(&memories, memory.categories())
+            }
+            JiyiAction::Update => {
+                let id = require_id(&request.id)?;
+                let content = request.content.trim();
+                let has_fields = !content.is_empty() || filter.category.is_some() || request.tags.is_some();
+                // 参数只会解析出 Never 或 At，Never 表示清除过期时间
+                let expires_at = parse_expiry(&request)?.map(|expiry| match expiry {
+                    Expiry::At(at) => Some(at),
+                    Expiry::Default | Expiry::Never => None,
+                });
+                if !has_fields && expires_at.is_none() {
+                    return Err(McpError::invalid_params(t(Msg::JiUpdateMissingFields).to_string(), None));
+                }
+
+                let layer = locate_layer(&memory, id, scope)?;
+                let (entry, secrets) = layer
+                    .update_memory_with_expiry(id, (!content.is_empty()).then_some(content), filter.category.clone(), request.tags.clone(), expires_at)
+                    .map_err(|e| McpError::invalid_params(tf(Msg::JiUpdateFailed, &[&e]), None))?;
+
+                tf(Msg::JiUpdated, &[&entry.id, &entry.content, &entry.category])
+                    + &format_tags(&entry.tags)
+                    + &format_expiry(entry.expires_at)
+                    + &format_secrets(&secrets)
+            }
+            JiyiAction::Delete => {
+                let id = require_id(&request.id)?;
+                let entry = locate_layer(&memory, id, scope)?.delete_memory(id)
+                    .map_err(|e| McpError::invalid_params(tf(Msg::JiDeleteFailed, &[&e]), None))?;
+
+                tf(Msg::JiDeleted, &[&entry.id, &entry.content])
+            }
+            JiyiAction::Move => {
+                let id = require_id(&request.id)?;
+                let category = filter.category.clone()
+                    .ok_or_else(|| McpError::invalid_params(t(Msg::JiMoveMissingCategory).to_string(), None))?;
+
+                let entry = locate_layer(&memory, id, scope)?.move_memory(id, category)
+                    .map_err(|e| McpError::invalid_params(tf(Msg::JiMoveFailed, &[&e]), None))?;
+
+                tf(Msg::JiMoved, &[&entry.category.key(), &entry.id])
+            }
+            JiyiAction::Promote => {
+                let id = require_id(&request.id)?;
+                let entry = locate_layer(&memory, id, scope)?.promote_memory(id)
+                    .map_err(|e| McpError::invalid_params(tf(Msg::JiPromoteFailed, &[&e]), None))?;
+
+                tf(Msg::JiPromoted, &[&entry.id, &entry.content])
+            }
+            JiyiAction::History => {
+                let id = request.id.trim();
+                let records = memory
+                    .history(scope, (!id.is_empty()).then_some(id), request.limit.filter(|&n| n > 0).unwrap_or(DEFAULT_HISTORY_LIMIT))
+                    .map_err(|e| McpError::internal_error(tf(Msg::JiHistoryFailed, &[&e]), None))?;
+
+                format_history(&records)
+            }
+            JiyiAction::Undo => {
+                let change_id = request.id.trim();
+                if change_id.is_empty() {
+                    return Err(McpError::invalid_params(t(Msg::JiMissingChangeId).to_string(), None));
                 }
 
-                let category = match request.category.as_str() {
-                    "rule" => MemoryCategory::Rule,
-                    "preference" => MemoryCategory::Preference,
-                    "pattern" => MemoryCategory::Pattern,
-                    "context" => MemoryCategory::Context,
-                    _ => MemoryCategory::Context,
+                let undo = memory
+                    .locate_change(change_id, scope)
+                    .and_then(|layer| layer.undo_change(change_id))
+                    .map_err(|e| McpError::invalid_params(tf(Msg::JiUndoFailed, &[&e]), None))?;
+
+                let state = match &undo.after {
+                    Some(entry) => entry.content.as_str(),
+                    None => t(Msg::JiUndoneDeleted),
                 };
+                tf(Msg::JiUndone, &[&change_id, &undo.memory_id, &state, &undo.id])
+            }
+            JiyiAction::Organize => {
+                let archive_days = memory_config().archive_context_days;
+                let plan_id = request.plan_id.trim();
 
-                let id = manager.add_memory(&request.content, category)
-                    .map_err(|e| McpError::internal_error(format!("添加记忆失败: {}", e), None))?;
+                let report = write_layer(&memory, scope)?
+                    .organize(archive_days, (!plan_id.is_empty()).then_some(plan_id))
+                    .map_err(|e| McpError::invalid_params(tf(Msg::JiOrganizeFailed, &[&e]), None))?;
 
-                format!("✅ 记忆已添加，ID: {}\n📝 内容: {}\n📂 分类: {:?}", id, request.content, category)
+                format_organize_report(&report)
             }
-            "回忆" => {
-                manager.get_project_info()
-                    .map_err(|e| McpError::internal_error(format!("获取项目信息失败: {}", e), None))?
+            JiyiAction::Import => {
+                let manager = write_layer(&memory, scope)?;
+                let (root, files) = rules_files(&request.project_path, request.rules_file.as_deref(), false)?;
+
+                let mut lines = Vec::new();
+                for file in files {
+                    let report = agent_rules::import_rules(manager, &root, file)
+                        .map_err(|e| McpError::internal_error(tf(Msg::JiImportFailed, &[&e]), None))?;
+                    lines.push(tf(Msg::JiImportLine, &[&report.file, &report.imported, &report.skipped]));
+                }
+                tf(Msg::JiImportDone, &[&lines.join("\n")])
             }
-            _ => {
-                return Err(McpError::invalid_params(
-                    format!("未知的操作类型: {}", request.action),
-                    None
-                ));
+            JiyiAction::Export => {
+                let manager = write_layer(&memory, scope)?;
+                let (root, files) = rules_files(&request.project_path, request.rules_file.as_deref(), true)?;
+
+                let mut lines = Vec::new();
+                for file in files {
+                    let report = agent_rules::export_rules(manager, &root, file)
+                        .map_err(|e| McpError::internal_error(tf(Msg::JiExportFailed, &[&e]), None))?;
+                    let line = if report.created { Msg::JiExportCreated } else { Msg::JiExportUpdated };
+                    lines.push(tf(line, &[&report.file, &report.exported]));
+                }
+                tf(Msg::JiExportDone, &[&lines.join("\n")])
             }
         };
 
         Ok(CallToolResult::success(vec![Content::text(result)]))
     }
 }
+
+/// 解析分支参数，"current" 表示项目当前所在分支
+fn resolve_branch(memory: &LayeredMemory, branch: Option<&str>) -> Result<Option<String>, McpError> {
+    match branch.map(str::trim).filter(|b| !b.is_empty()) {
+        None => Ok(None),
+        Some("current") => memory
+            .current_branch()
+            .map(|branch| Some(branch.to_string()))
+            .ok_or_else(|| McpError::invalid_params(t(Msg::JiUnknownBranch).to_string(), None)),
+        Some(branch) => Ok(Some(branch.to_string())),
+    }
+}
+
+/// 解析有效期参数，未提供时返回 None
+fn parse_expiry(request: &JiyiRequest) -> Result<Option<Expiry>, McpError> {
+    Expiry::from_params(request.ttl.as_deref(), request.expires_at.as_deref(), Utc::now())
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))
+}
+
+/// 格式化过期时间，永不过期时为空
+fn format_expiry(expires_at: Option<DateTime<Utc>>) -> String {
+    expires_at
+        .map(|at| tf(Msg::JiExpiresAt, &[&at.format("%Y-%m-%d %H:%M UTC")]))
+        .unwrap_or_default()
+}
+
+/// 写入时遮盖了疑似密钥则附加提示
+fn format_secrets(findings: &[SecretFinding]) -> String {
+    if findings.is_empty() {
+        String::new()
+    } else {
+        tf(Msg::JiSecretsMasked, &[&findings.len(), &secrets::describe(findings)])
+    }
+}
+
+/// 读取记忆配置，失败时使用默认值
+fn memory_config() -> MemoryConfig {
+    load_standalone_config()
+        .map(|config| config.memory_config)
+        .unwrap_or_else(|_| default_memory_config())
+}
+
+/// 解析分类参数，未提供时返回 None，无法识别时返回参数错误
+fn parse_category(memory: &LayeredMemory, category: Option<&str>) -> Result<Option<MemoryCategory>, McpError> {
+    match category.map(str::trim).filter(|c| !c.is_empty()) {
+        None => Ok(None),
+        Some(key) => memory.categories().resolve(key).map(Some).ok_or_else(|| {
+            McpError::invalid_params(tf(Msg::JiUnknownCategory, &[&key, &memory.categories().keys().join(", ")]), None)
+        }),
+    }
+}
+
+/// 格式化标签，没有标签时为空
+fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        String::new()
+    } else {
+        tf(Msg::JiTags, &[&tags.join(", ")])
+    }
+}
+
+/// 解析要导入或导出的规则文件，未指定时使用项目中已存在的全部规则文件
+fn rules_files(project_path: &str, rules_file: Option<&str>, exporting: bool) -> Result<(PathBuf, Vec<AgentRulesFile>), McpError> {
+    let root = MemoryManager::resolve_git_root(project_path)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+    let files = match rules_file.map(str::trim).filter(|f| !f.is_empty()) {
+        Some(key) => vec![AgentRulesFile::from_key(key).ok_or_else(|| {
+            McpError::invalid_params(
+                tf(Msg::JiUnknownRulesFile, &[&key, &AgentRulesFile::ALL.map(|f| f.key()).join(", ")]),
+                None,
+            )
+        })?],
+        None => AgentRulesFile::existing(&root),
+    };
+
+    if files.is_empty() {
+        let msg = if exporting { Msg::JiNoRulesFileToExport } else { Msg::JiNoRulesFile };
+        return Err(McpError::invalid_params(t(msg).to_string(), None));
+    }
+    Ok((root, files))
+}
+
+/// 写入操作使用的作用域，默认为项目
+fn write_layer(memory: &LayeredMemory, scope: Option<MemoryScope>) -> Result<&MemoryManager, McpError> {
+    memory
+        .layer(scope.unwrap_or_default())
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))
+}
+
+/// 查找记忆所在作用域
+fn locate_layer<'a>(memory: &'a LayeredMemory, id: &str, scope: Option<MemoryScope>) -> Result<&'a MemoryManager, McpError> {
+    memory
+        .locate(id, scope)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))
+}
+
+/// 校验记忆ID参数
+fn require_id(id: &str) -> Result<&str, McpError> {
+    let id = id.trim();
+    if id.is_empty() {
+        return Err(McpError::invalid_params(t(Msg::JiMissingId).to_string(), None));
+    }
+    Ok(id)
+}
+
+/// 格式化添加结果，说明去重和冲突检测的判断
+fn format_add_outcome(
+    outcome: &AddOutcome,
+    category: &MemoryCategory,
+    scope: MemoryScope,
+    branch: Option<&str>,
+) -> String {
+    match outcome {
+        AddOutcome::Added { id, content, expires_at, conflicts } => {
+            let mut output = tf(Msg::JiAdded, &[id, content, category]);
+            output.push_str(&format_expiry(*expires_at));
+            if scope != MemoryScope::Project {
+                output.push_str(&tf(Msg::JiAddedScope, &[&scope.key()]));
+            }
+            if let Some(branch) = branch {
+                output.push_str(&tf(Msg::JiAddedBranch, &[&branch]));
+            }
+            if !conflicts.is_empty() {
+                output.push_str(t(Msg::JiAddedConflicts));
+                for conflict in conflicts {
+                    output.push_str(&format!("\n- [{}] {}", conflict.id, conflict.content));
+                }
+            }
+            output
+        }
+        AddOutcome::Duplicate(similar) => tf(
+            Msg::JiDuplicate,
+            &[&format!("{:.0}", similar.similarity * 100.0), &similar.entry.id, &similar.entry.content],
+        ),
+        AddOutcome::Merged(similar) => tf(
+            Msg::JiMerged,
+            &[&format!("{:.0}", similar.similarity * 100.0), &similar.entry.id, &similar.entry.content],
+        ),
+    }
+}
+
+/// 格式化整理结果，预览时提示如何确认执行
+fn format_organize_report(report: &OrganizeReport) -> String {
+    if !report.has_changes() {
+        return t(Msg::JiOrganizeClean).to_string();
+    }
+
+    let mut output = report.to_markdown();
+    match &report.report_path {
+        Some(path) => output.push_str(&tf(Msg::JiOrganizeWritten, &[path])),
+        None => output.push_str(&tf(Msg::JiOrganizePreview, &[&report.plan_id])),
+    }
+    output
+}
+
+/// 检索结果数量，未指定或为0时使用默认值
+fn search_limit(limit: Option<usize>) -> usize {
+    limit.filter(|&n| n > 0).unwrap_or(DEFAULT_SEARCH_LIMIT)
+}
+
+/// 格式化检索结果
+fn format_search_hits(query: &str, hits: &[SearchHit]) -> String {
+    if hits.is_empty() {
+        return tf(Msg::JiSearchEmpty, &[&query]);
+    }
+
+    let mut output = tf(Msg::JiSearchFound, &[&hits.len(), &query]);
+    for (index, hit) in hits.iter().enumerate() {
+        let content = hit.entry.content.split_whitespace().collect::<Vec<&str>>().join(" ");
+        output.push_str(&format!(
+            "\n{}. [{}/{}] [{}] {} ({})",
+            index + 1,
+            hit.entry.scope.key(),
+            hit.entry.category.key(),
+            hit.entry.id,
+            content,
+            tf(Msg::JiSearchScore, &[&format!("{:.2}", hit.score)])
+        ));
+    }
+    output
+}
+
+/// 格式化变更历史，最近的在前
+fn format_history(records: &[ChangeRecord]) -> String {
+    if records.is_empty() {
+        return t(Msg::JiHistoryEmpty).to_string();
+    }
+
+    let mut output = tf(Msg::JiHistoryHeader, &[&records.len()]);
+    for record in records {
+        let content = record.content().split_whitespace().collect::<Vec<&str>>().join(" ");
+        let scope = if record.scope == MemoryScope::Project { String::new() } else { format!(" ({})", record.scope.label()) };
+        let undoes = record.undoes.as_ref().map(|id| tf(Msg::JiHistoryUndoes, &[id])).unwrap_or_default();
+        output.push_str(&format!(
+            "\n- [{}] {} {}{} {} [{}] {}{}",
+            record.id,
+            record.timestamp.format("%Y-%m-%d %H:%M"),
+            record.source.label(),
+            scope,
+            record.kind.label(),
+            record.memory_id,
+            content,
+            undoes
+        ));
+    }
+    output
+}
+
+/// 按分类格式化记忆列表
+fn format_memory_list(memories: &[MemoryEntry], categories: &CategoryRegistry) -> String {
+    if memories.is_empty() {
+        return t(Msg::JiListEmpty).to_string();
+    }
+
+    let now = Utc::now();
+    let mut output = tf(Msg::JiListHeader, &[&memories.len()]);
+    for category in categories.all() {
+        let items: Vec<&MemoryEntry> = memories.iter().filter(|m| m.category == category).collect();
+        if items.is_empty() {
+            continue;
+        }
+
+        output.push_str(&format!("\n\n## {} ({})", category, categories.title(&category)));
+        for item in items {
+            let mut content = item.content.split_whitespace().collect::<Vec<&str>>().join(" ");
+            for tag in &item.tags {
+                content.push_str(&format!(" #{}", tag));
+            }
+            if let Some(branch) = &item.branch {
+                content.push_str(&tf(Msg::JiListBranch, &[branch]));
+            }
+            match item.expires_at {
+                Some(_) if item.is_expired(now) => content.push_str(t(Msg::JiListExpired)),
+                Some(at) => content.push_str(&tf(Msg::JiListExpiresOn, &[&at.format("%Y-%m-%d")])),
+                None => {}
+            }
+            if item.scope == MemoryScope::Project {
+                output.push_str(&format!("\n- [{}] {}", item.id, content));
+            } else {
+                output.push_str(&format!("\n- [{}] ({}) {}", item.id, item.scope.label(), content));
+            }
+        }
+    }
+    output
+}
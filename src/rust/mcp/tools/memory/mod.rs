@@ -2,11 +2,41 @@
 //!
 //! 提供全局记忆管理功能，用于存储和管理重要的开发规范、用户偏好和最佳实践
 
+pub mod agent_rules;
+pub mod budget;
+pub mod category;
+pub mod dedup;
+pub mod expiry;
+pub mod format;
+pub mod history;
+pub mod layout;
+pub mod location;
 pub mod manager;
+pub mod organize;
+pub mod registry;
+pub mod scope;
+pub mod search;
+pub mod secrets;
+pub mod store;
+#[cfg(test)]
+mod test_support;
 pub mod types;
 pub mod mcp;
 
 // 重新导出主要类型和功能
-pub use manager::MemoryManager;
-pub use types::{MemoryEntry, MemoryCategory, MemoryMetadata};
+pub use agent_rules::{AgentRulesFile, RulesExportReport, RulesImportReport};
+pub use budget::RecallBudget;
+pub use category::CategoryRegistry;
+pub use dedup::SimilarMemory;
+pub use expiry::Expiry;
+pub use history::{ChangeKind, ChangeRecord, ChangeSource, DEFAULT_HISTORY_LIMIT};
+pub use layout::MemoryLayout;
+pub use location::{migrate_project_memory, MigrationReport, ProjectStorage};
+pub use manager::{AddOutcome, MemoryManager};
+pub use organize::OrganizeReport;
+pub use registry::{known_projects, KnownProject};
+pub use scope::{LayeredMemory, MemoryFilter};
+pub use search::{SearchHit, DEFAULT_SEARCH_LIMIT};
+pub use secrets::SecretPolicy;
+pub use types::{DuplicatePolicy, MemoryEntry, MemoryCategory, MemoryMetadata, MemoryScope};
 pub use mcp::MemoryTool;
@@ -0,0 +1,229 @@
+//! 记忆整理
+//!
+//! 整理分两步：先以 dry run 生成计划和差异，确认后携带计划ID再次调用才会改写文件。
+//! 计划ID由当前文件内容、整理参数和整理结果计算，文件在两次调用之间被修改时需要重新预览
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::dedup;
+use super::types::MemoryEntry;
+use crate::i18n::{t, tf, Msg};
+
+/// 归档目录名，位于记忆目录下，文件名与分类文件相同
+pub const ARCHIVE_DIR_NAME: &str = "archive";
+
+/// 最近一次整理的报告文件名
+pub const ORGANIZE_REPORT_FILE: &str = "organize-report.md";
+
+/// 单个分类的整理结果
+#[derive(Debug, Clone, Default)]
+pub struct CategoryPlan {
+    /// 整理后保留的条目
+    pub kept: Vec<MemoryEntry>,
+    /// 需要归档的条目
+    pub archived: Vec<MemoryEntry>,
+    /// 已过期、需要删除的条目
+    pub expired: Vec<MemoryEntry>,
+    /// 被合并掉的重复条目
+    pub duplicates: Vec<MemoryEntry>,
+    /// 空白被规范化的条目数
+    pub normalized: usize,
+}
+
+/// 整理报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OrganizeReport {
+    /// 确认执行时需要携带的计划ID
+    pub plan_id: String,
+    /// 是否已写入文件
+    pub applied: bool,
+    pub duplicates_removed: usize,
+    pub whitespace_normalized: usize,
+    pub archived: usize,
+    pub expired_removed: usize,
+    /// 各文件的差异
+    pub diff: String,
+    /// 报告文件路径，仅在执行后存在
+    pub report_path: Option<String>,
+}
+
+impl OrganizeReport {
+    /// 是否有需要改写的内容
+    pub fn has_changes(&self) -> bool {
+        !self.diff.is_empty()
+    }
+
+    /// 生成 Markdown 报告
+    pub fn to_markdown(&self) -> String {
+        let mut output = tf(
+            Msg::JiOrganizeReport,
+            &[
+                &self.plan_id,
+                &t(if self.applied { Msg::JiOrganizeApplied } else { Msg::JiOrganizeUnapplied }),
+                &self.duplicates_removed,
+                &self.whitespace_normalized,
+                &self.archived,
+                &self.expired_removed,
+            ],
+        );
+        if self.has_changes() {
+            output.push_str(&format!("\n```diff\n{}```\n", self.diff));
+        } else {
+            output.push_str(t(Msg::JiOrganizeNothing));
+        }
+        output
+    }
+}
+
+/// 归档文件的标题
+pub fn archive_title(title: &str) -> String {
+    tf(Msg::JiArchivedTitle, &[&title])
+}
+
+/// 规范化空白：去除行首尾空白、合并连续空格，连续空行合并为一行，首尾空行去掉
+///
+/// 多行记忆中的空行用于分段，保留下来
+pub fn normalize_whitespace(content: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if line.is_empty() && !matches!(lines.last(), Some(last) if !last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    if lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// 整理单个分类的条目
+///
+/// 重复条目保留最早创建的一条（ID稳定），内容取信息更完整的一方，标签取并集。
+/// 在 `now` 之前过期的条目会被删除；`archive_before` 不为空时，更新时间早于该时间的条目会被归档
+pub fn plan_category(mut entries: Vec<MemoryEntry>, archive_before: Option<DateTime<Utc>>, now: DateTime<Utc>) -> CategoryPlan {
+    let mut plan = CategoryPlan::default();
+
+    entries.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.content.cmp(&b.content)));
+
+    for mut entry in entries {
+        if entry.is_expired(now) {
+            plan.expired.push(entry);
+            continue;
+        }
+
+        let normalized = normalize_whitespace(&entry.content);
+        if normalized != entry.content {
+            entry.content = normalized;
+            plan.normalized += 1;
+        }
+
+        if archive_before.is_some_and(|before| entry.updated_at < before) {
+            plan.archived.push(entry);
+            continue;
+        }
+
+        // 只合并同一分支的重复条目，分支记忆与通用记忆的可见范围不同
+        let same_branch: Vec<MemoryEntry> = plan.kept.iter().filter(|kept| kept.branch == entry.branch).cloned().collect();
+        match dedup::find_duplicate(&entry.content, &same_branch) {
+            Some(similar) => {
+                let kept = plan
+                    .kept
+                    .iter_mut()
+                    .find(|kept| kept.id == similar.entry.id)
+                    .expect("重复条目来自已保留列表");
+                kept.content = dedup::merge_content(&kept.content, &entry.content);
+                for tag in &entry.tags {
+                    if !kept.tags.iter().any(|own| own.eq_ignore_ascii_case(tag)) {
+                        kept.tags.push(tag.clone());
+                    }
+                }
+                kept.updated_at = kept.updated_at.max(entry.updated_at);
+                plan.duplicates.push(entry);
+            }
+            None => plan.kept.push(entry),
+        }
+    }
+
+    plan
+}
+
+/// 按行比较两段文本，只输出变化的行
+pub fn line_diff(label: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    // 最长公共子序列
+    let mut lcs = vec![vec![0usize; new_lines.len() + 1]; old_lines.len() + 1];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = format!("--- a/{}\n+++ b/{}\n", label, label);
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() || j < new_lines.len() {
+        if i < old_lines.len() && j < new_lines.len() && old_lines[i] == new_lines[j] {
+            i += 1;
+            j += 1;
+        } else if i < old_lines.len() && (j == new_lines.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            output.push_str(&format!("-{}\n", old_lines[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("+{}\n", new_lines[j]));
+            j += 1;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::entry;
+    use chrono::Duration;
+
+    #[test]
+    fn test_plan_merges_duplicates_archives_stale_and_drops_expired() {
+        let entries = vec![
+            entry("使用  pnpm 安装依赖").id("new").age_days(1).build(),
+            entry("使用 pnpm 安装依赖").id("old").age_days(5).build(),
+            entry("旧的部署地址").id("stale").age_days(400).build(),
+            entry("后端使用 Rust").id("other").age_days(2).build(),
+            entry("正在排查 issue 123").id("done").age_days(3).expires_at(Utc::now() - Duration::hours(1)).build(),
+        ];
+
+        let plan = plan_category(entries, Some(Utc::now() - Duration::days(90)), Utc::now());
+        assert_eq!(plan.kept.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), vec!["old", "other"]);
+        assert_eq!(plan.duplicates[0].id, "new");
+        assert_eq!(plan.archived[0].id, "stale");
+        assert_eq!(plan.expired[0].id, "done");
+        assert_eq!(plan.normalized, 1);
+    }
+
+    #[test]
+    fn test_normalize_keeps_paragraph_breaks() {
+        assert_eq!(normalize_whitespace("\n发布步骤：  \n\n\n1.  打标签\n   \n2. 推送\n\n"), "发布步骤：\n\n1. 打标签\n\n2. 推送");
+
+        let steps = entry("发布步骤：\n\n1. 打标签\n\n2. 推送").id("steps").age_days(1).build();
+        assert_eq!(plan_category(vec![steps], None, Utc::now()).normalized, 0);
+    }
+
+    #[test]
+    fn test_line_diff_only_shows_changes() {
+        let diff = line_diff("rules.md", "# 标题\n- a\n- b\n", "# 标题\n- b\n- c\n");
+        assert_eq!(diff, "--- a/rules.md\n+++ b/rules.md\n-- a\n+- c\n");
+        assert!(line_diff("rules.md", "same", "same").is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+//! 已知项目登记
+//!
+//! 仓库内的记忆目录分散在各个项目中，打开项目记忆时在应用数据目录登记一次，
+//! 供设置界面列出使用过记忆功能的项目。迁移过存储位置的项目同时记录迁移后的位置
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::location::ProjectStorage;
+use super::store::{atomic_write, lock_dir};
+
+/// 登记文件名
+pub const REGISTRY_FILE: &str = "known_projects.json";
+
+/// 使用过记忆功能的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownProject {
+    /// git 根目录
+    pub project_path: String,
+    pub memory_dir: String,
+    pub last_used: DateTime<Utc>,
+    /// 迁移后选定的存储位置，未迁移过时使用配置的位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<String>,
+}
+
+/// 登记文件所在目录
+fn registry_dir() -> Result<PathBuf> {
+    // 测试中登记到临时目录，不污染本机应用数据
+    if cfg!(test) {
+        return Ok(std::env::temp_dir().join(format!("cunzhi-test-registry-{}", std::process::id())));
+    }
+
+    Ok(dirs::data_dir()
+        .ok_or_else(|| anyhow::anyhow!("无法获取应用数据目录"))?
+        .join("cunzhi")
+        .join("memory"))
+}
+
+fn read_registry(path: &Path) -> Vec<KnownProject> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 登记项目，已登记的项目只更新记忆目录和使用时间
+pub fn record_project(project_path: &str, memory_dir: &Path) -> Result<()> {
+    update_project(project_path, memory_dir, |_| {})
+}
+
+/// 登记项目迁移后的存储位置，之后打开该项目时使用这个位置
+pub fn record_project_storage(project_path: &str, memory_dir: &Path, storage: ProjectStorage) -> Result<()> {
+    update_project(project_path, memory_dir, |project| project.storage = Some(storage.key().to_string()))
+}
+
+/// 项目登记的存储位置，未迁移过时为空
+pub fn project_storage(project_path: &str) -> Option<ProjectStorage> {
+    read_registry(&registry_dir().ok()?.join(REGISTRY_FILE))
+        .into_iter()
+        .find(|p| p.project_path == project_path)
+        .and_then(|p| p.storage)
+        .and_then(|storage| ProjectStorage::from_key(&storage))
+}
+
+fn update_project(project_path: &str, memory_dir: &Path, update: impl FnOnce(&mut KnownProject)) -> Result<()> {
+    let dir = registry_dir()?;
+    fs::create_dir_all(&dir)?;
+    let _lock = lock_dir(&dir)?;
+
+    let path = dir.join(REGISTRY_FILE);
+    let mut projects = read_registry(&path);
+    let memory_dir = memory_dir.to_string_lossy().to_string();
+    let index = match projects.iter().position(|p| p.project_path == project_path) {
+        Some(index) => index,
+        None => {
+            projects.push(KnownProject {
+                project_path: project_path.to_string(),
+                memory_dir: memory_dir.clone(),
+                last_used: Utc::now(),
+                storage: None,
+            });
+            projects.len() - 1
+        }
+    };
+    let project = &mut projects[index];
+    project.memory_dir = memory_dir;
+    project.last_used = Utc::now();
+    update(project);
+
+    atomic_write(&path, serde_json::to_string_pretty(&projects)?)
+}
+
+/// 已登记且记忆目录仍然存在的项目，最近使用的在前
+pub fn known_projects() -> Result<Vec<KnownProject>> {
+    let mut projects: Vec<KnownProject> = read_registry(&registry_dir()?.join(REGISTRY_FILE))
+        .into_iter()
+        .filter(|p| Path::new(&p.memory_dir).is_dir())
+        .collect();
+    projects.sort_by_key(|p| std::cmp::Reverse(p.last_used));
+    Ok(projects)
+}
@@ -0,0 +1,366 @@
+//! 记忆作用域分层
+//!
+//! 项目记忆存放在仓库内，团队记忆存放在配置的共享目录，全局记忆存放在用户配置目录下单独的子目录。
+//! 读取时按 项目 > 团队 > 全局 的优先级合并，低优先级中与高优先级重复的条目会被忽略
+
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::budget::{self, RecallBudget};
+use super::category::CategoryRegistry;
+use super::dedup;
+use super::history::{ChangeRecord, ChangeSource};
+use super::location::{current_branch, APP_DATA_PROJECTS_DIR};
+use super::manager::MemoryManager;
+use super::registry::REGISTRY_FILE;
+use super::search::{self, SearchHit};
+use super::store::LOCK_FILE;
+use super::types::{MemoryCategory, MemoryEntry, MemoryScope};
+use crate::config::{default_memory_config, load_standalone_config, MemoryConfig};
+use crate::i18n::{t, tf, Msg};
+use crate::{log_debug, log_important};
+
+/// 全局记忆所在的子目录
+const GLOBAL_DIR_NAME: &str = "global";
+
+/// 全局记忆目录
+///
+/// macOS 和 Windows 上配置目录与应用数据目录相同，项目登记和应用数据目录中的项目记忆
+/// 也在 `cunzhi/memory` 下，全局记忆单独放在子目录中。旧版本直接放在 `cunzhi/memory` 下的全局记忆会被移入
+pub fn global_memory_dir() -> Result<PathBuf> {
+    // 测试中使用临时目录，不读写本机的全局记忆
+    if cfg!(test) {
+        return Ok(std::env::temp_dir().join(format!("cunzhi-test-global-{}", std::process::id())));
+    }
+
+    let parent = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!(t(Msg::JiErrNoConfigDir)))?
+        .join("cunzhi")
+        .join("memory");
+    let dir = parent.join(GLOBAL_DIR_NAME);
+    if !dir.exists() {
+        migrate_legacy_global_dir(&parent, &dir);
+    }
+    Ok(dir)
+}
+
+/// 把旧版本放在 `legacy` 中的全局记忆移入 `target`，项目登记、应用数据目录中的项目记忆和锁文件留在原处
+///
+/// 多个进程可能同时迁移，已被其他进程移走的文件直接跳过；失败只记录日志，旧文件仍在原处
+fn migrate_legacy_global_dir(legacy: &Path, target: &Path) {
+    let Ok(dir_entries) = fs::read_dir(legacy) else {
+        return;
+    };
+    let legacy_files: Vec<PathBuf> = dir_entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            ![GLOBAL_DIR_NAME, APP_DATA_PROJECTS_DIR, REGISTRY_FILE, LOCK_FILE].iter().any(|kept| name == *kept)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    if legacy_files.is_empty() {
+        return;
+    }
+
+    if let Err(e) = fs::create_dir_all(target) {
+        log_important!(warn, "无法创建全局记忆目录: {}\n错误: {}", target.display(), e);
+        return;
+    }
+    for source in legacy_files {
+        let Some(name) = source.file_name() else { continue };
+        match fs::rename(&source, target.join(name)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log_important!(warn, "迁移全局记忆失败: {}\n错误: {}", source.display(), e),
+        }
+    }
+    log_important!(info, "全局记忆已移至: {}", target.display());
+}
+
+/// 团队记忆目录，未配置时返回 None
+pub fn team_memory_dir(config: &MemoryConfig) -> Option<PathBuf> {
+    let dir = config.team_dir.trim();
+    (!dir.is_empty()).then(|| PathBuf::from(dir))
+}
+
+/// 记忆筛选条件
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFilter {
+    /// 只读取指定作用域，未指定时按优先级合并全部作用域
+    pub scope: Option<MemoryScope>,
+    pub category: Option<MemoryCategory>,
+    /// 条目需要包含全部标签
+    pub tags: Vec<String>,
+    /// 包含其他分支的分支记忆，默认只包含当前分支可见的条目
+    pub all_branches: bool,
+    /// 包含已过期但尚未被整理删除的条目
+    pub include_expired: bool,
+    /// 保留与高优先级作用域重复的低优先级条目，列表展示时需要看到每个作用域的全部条目
+    pub keep_overridden: bool,
+}
+
+/// 按优先级组合的多作用域记忆
+pub struct LayeredMemory {
+    layers: Vec<MemoryManager>,
+    categories: CategoryRegistry,
+    /// 项目当前所在分支
+    branch: Option<String>,
+}
+
+impl LayeredMemory {
+    /// 打开项目记忆以及已配置的团队、全局记忆
+    pub fn open(project_path: &str) -> Result<Self> {
+        let config = load_standalone_config()
+            .map(|c| c.memory_config)
+            .unwrap_or_else(|_| default_memory_config());
+
+        let project = MemoryManager::new(project_path)?;
+        project.register_project();
+
+        let mut layers = vec![project];
+        if let Some(team_dir) = team_memory_dir(&config) {
+            layers.push(MemoryManager::with_dir(team_dir, MemoryScope::Team)?.with_config(&config));
+        }
+        // 全局记忆目录在第一次写入全局记忆时才创建
+        layers.push(MemoryManager::with_dir_lazy(global_memory_dir()?, MemoryScope::Global)?.with_config(&config));
+
+        Ok(Self::from_layers(project_path, layers))
+    }
+
+    /// 以只读方式打开已有的项目、团队和全局记忆，不创建目录或文件，也不登记项目
+    pub fn open_existing(project_path: &str) -> Result<Self> {
+        let config = load_standalone_config()
+            .map(|c| c.memory_config)
+            .unwrap_or_else(|_| default_memory_config());
+
+        let mut layers: Vec<MemoryManager> = MemoryManager::open_existing(project_path)?.into_iter().collect();
+        if let Some(team_dir) = team_memory_dir(&config) {
+            layers.extend(MemoryManager::open_existing_dir(team_dir, MemoryScope::Team));
+        }
+        layers.extend(MemoryManager::open_existing_dir(global_memory_dir()?, MemoryScope::Global));
+
+        Ok(Self::from_layers(project_path, layers))
+    }
+
+    fn from_layers(project_path: &str, layers: Vec<MemoryManager>) -> Self {
+        // 各作用域可能声明了不同的分类，合并后用于参数校验和展示
+        let mut categories = layers.first().map(|layer| layer.categories().clone()).unwrap_or_else(CategoryRegistry::builtin);
+        for layer in layers.iter().skip(1) {
+            categories.merge_missing(layer.categories());
+        }
+
+        // 只读取本地 HEAD，不访问远程
+        let branch = MemoryManager::resolve_git_root(project_path)
+            .ok()
+            .and_then(|git_root| current_branch(&git_root));
+
+        log_debug!("已加载记忆作用域: {:?}，当前分支: {:?}", layers.iter().map(|l| l.scope()).collect::<Vec<_>>(), branch);
+        Self { layers, categories, branch }
+    }
+
+    /// 指定后续写入在变更历史中记录的来源
+    pub fn with_source(mut self, source: ChangeSource) -> Self {
+        self.layers = self.layers.into_iter().map(|layer| layer.with_source(source)).collect();
+        self
+    }
+
+    /// 项目当前所在分支，分离头指针时为空
+    pub fn current_branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    /// 所有作用域中可用的分类
+    pub fn categories(&self) -> &CategoryRegistry {
+        &self.categories
+    }
+
+    /// 获取指定作用域的记忆管理器
+    pub fn layer(&self, scope: MemoryScope) -> Result<&MemoryManager> {
+        self.layers
+            .iter()
+            .find(|layer| layer.scope() == scope)
+            .ok_or_else(|| match scope {
+                MemoryScope::Team => anyhow::anyhow!(t(Msg::JiErrTeamNotConfigured)),
+                _ => anyhow::anyhow!(tf(Msg::JiErrScopeUnavailable, &[&scope.key()])),
+            })
+    }
+
+    /// 选中的作用域，未指定时为全部作用域
+    fn selected(&self, scope: Option<MemoryScope>) -> Result<Vec<&MemoryManager>> {
+        match scope {
+            Some(scope) => Ok(vec![self.layer(scope)?]),
+            None => Ok(self.layers.iter().collect()),
+        }
+    }
+
+    /// 按优先级合并记忆
+    pub fn get_memories(&self, filter: &MemoryFilter) -> Result<Vec<MemoryEntry>> {
+        let now = Utc::now();
+        let visible = |entry: &MemoryEntry| {
+            entry.has_tags(&filter.tags)
+                && (filter.all_branches || entry.visible_on(self.branch.as_deref()))
+                && (filter.include_expired || !entry.is_expired(now))
+        };
+
+        let mut layers = Vec::new();
+        for layer in self.selected(filter.scope)? {
+            let entries = match &filter.category {
+                Some(category) => layer.get_memories_by_category(category)?,
+                None => layer.get_memories_in_priority_order()?,
+            };
+            layers.push(entries.into_iter().filter(|entry| visible(entry)).collect());
+        }
+
+        if filter.keep_overridden {
+            return Ok(layers.into_iter().flatten().collect());
+        }
+        Ok(merge_layers(layers))
+    }
+
+    /// 查找记忆所在的作用域
+    pub fn locate(&self, id: &str, scope: Option<MemoryScope>) -> Result<&MemoryManager> {
+        for layer in self.selected(scope)? {
+            if layer.find_memory(id.trim())?.is_some() {
+                return Ok(layer);
+            }
+        }
+        Err(anyhow::anyhow!(tf(Msg::JiErrMemoryNotFound, &[&id.trim()])))
+    }
+
+    /// 合并各作用域的变更历史，最近的在前
+    pub fn history(&self, scope: Option<MemoryScope>, memory_id: Option<&str>, limit: usize) -> Result<Vec<ChangeRecord>> {
+        let mut records = Vec::new();
+        for layer in self.selected(scope)? {
+            records.extend(layer.history(memory_id)?);
+        }
+        records.sort_by_key(|record| std::cmp::Reverse(record.timestamp));
+        records.truncate(limit);
+        Ok(records)
+    }
+
+    /// 查找变更记录所在的作用域
+    pub fn locate_change(&self, change_id: &str, scope: Option<MemoryScope>) -> Result<&MemoryManager> {
+        for layer in self.selected(scope)? {
+            if layer.history(None)?.iter().any(|record| record.id == change_id.trim()) {
+                return Ok(layer);
+            }
+        }
+        Err(anyhow::anyhow!(tf(Msg::JiErrChangeNotFound, &[&change_id.trim()])))
+    }
+
+    /// 按相关度检索记忆
+    pub fn search_memories(&self, query: &str, filter: &MemoryFilter, limit: usize) -> Result<Vec<SearchHit>> {
+        Ok(search::rank(&self.get_memories(filter)?, query, limit))
+    }
+
+    /// 获取合并后的项目信息，超出预算时优先保留高优先级分类中最近更新的条目
+    pub fn get_project_info(&self, filter: &MemoryFilter, budget: RecallBudget) -> Result<String> {
+        let mut memories = self.get_memories(filter)?;
+        if memories.is_empty() {
+            return Ok(t(Msg::JiListEmpty).to_string());
+        }
+
+        memories.sort_by_key(|m| std::cmp::Reverse(m.updated_at));
+        budget::order_by_priority(&mut memories, &self.categories);
+        Ok(budget::summarize_within_budget(memories, &self.categories, budget))
+    }
+
+    /// 获取与查询相关的项目信息，超出预算时优先保留高优先级分类中相关度高的条目
+    pub fn get_project_info_matching(
+        &self,
+        query: &str,
+        filter: &MemoryFilter,
+        limit: usize,
+        budget: RecallBudget,
+    ) -> Result<String> {
+        let hits = self.search_memories(query, filter, limit)?;
+        if hits.is_empty() {
+            return Ok(tf(Msg::JiRecallEmpty, &[&query.trim()]));
+        }
+
+        let mut memories: Vec<MemoryEntry> = hits.into_iter().map(|hit| hit.entry).collect();
+        budget::order_by_priority(&mut memories, &self.categories);
+        Ok(budget::summarize_within_budget(memories, &self.categories, budget))
+    }
+}
+
+/// 按优先级从高到低合并各作用域的条目，忽略与更高优先级作用域中同分类条目重复的条目
+///
+/// 同一作用域内的相似条目都会保留，它们可能是有意添加的（如 `on_duplicate: allow`）
+fn merge_layers(layers: Vec<Vec<MemoryEntry>>) -> Vec<MemoryEntry> {
+    let mut merged = Vec::new();
+    let mut higher: HashMap<MemoryCategory, Vec<MemoryEntry>> = HashMap::new();
+    for entries in layers {
+        let kept: Vec<MemoryEntry> = entries
+            .into_iter()
+            .filter(|entry| match higher.get(&entry.category) {
+                Some(same_category) => dedup::find_duplicate(&entry.content, same_category).is_none(),
+                None => true,
+            })
+            .collect();
+        for entry in &kept {
+            higher.entry(entry.category.clone()).or_default().push(entry.clone());
+        }
+        merged.extend(kept);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{entry, TempDir};
+
+    #[test]
+    fn test_merge_only_hides_entries_overridden_by_higher_layers() {
+        let project = vec![
+            entry("Use pnpm to install dependencies").scope(MemoryScope::Project).build(),
+            entry("use pnpm to install dependencies.").scope(MemoryScope::Project).build(),
+        ];
+        let global = vec![
+            entry("Use pnpm to install dependencies!").scope(MemoryScope::Global).build(),
+            entry("提交前运行测试").scope(MemoryScope::Global).build(),
+        ];
+
+        let merged = merge_layers(vec![project, global]);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.iter().filter(|m| m.scope == MemoryScope::Project).count(), 2);
+        assert_eq!(merged[2].content, "提交前运行测试");
+    }
+
+    #[test]
+    fn test_legacy_global_memory_moves_into_its_own_dir() {
+        let temp = TempDir::new("memory-legacy-global");
+        let legacy = temp.path();
+        let target = legacy.join(GLOBAL_DIR_NAME);
+        fs::create_dir_all(legacy.join("archive")).unwrap();
+        fs::create_dir_all(legacy.join(APP_DATA_PROJECTS_DIR).join("app-1")).unwrap();
+        for file in ["rules.md", "categories.json", REGISTRY_FILE, LOCK_FILE] {
+            fs::write(legacy.join(file), file).unwrap();
+        }
+
+        migrate_legacy_global_dir(legacy, &target);
+        for moved in ["rules.md", "categories.json", "archive"] {
+            assert!(target.join(moved).exists() && !legacy.join(moved).exists(), "{}", moved);
+        }
+        for kept in [REGISTRY_FILE, LOCK_FILE, APP_DATA_PROJECTS_DIR] {
+            assert!(legacy.join(kept).exists() && !target.join(kept).exists(), "{}", kept);
+        }
+    }
+
+    #[test]
+    fn test_lazy_global_dir_is_created_on_first_write() {
+        let temp = TempDir::new("memory-lazy-global");
+        let dir = temp.path();
+        let global = MemoryManager::with_dir_lazy(dir.to_path_buf(), MemoryScope::Global).unwrap();
+        assert!(global.get_all_memories().unwrap().is_empty());
+        assert!(!dir.exists());
+
+        global.add_memory("提交前运行测试", MemoryCategory::RULE).unwrap();
+        assert_eq!(global.get_all_memories().unwrap().len(), 1);
+    }
+}
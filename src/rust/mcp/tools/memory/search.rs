@@ -0,0 +1,162 @@
+//! 记忆检索
+//!
+//! 使用 BM25 对记忆做本地词法打分。中日韩文字没有空格分词，按连续字符切出单字和相邻二元组，
+//! 其他文字按字母数字串切词并统一小写
+
+use std::collections::HashMap;
+
+use super::types::MemoryEntry;
+
+/// 默认返回的结果数量
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// 检索命中结果
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchHit {
+    pub entry: MemoryEntry,
+    pub score: f64,
+}
+
+/// 判断是否为中日韩文字
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // 日文假名
+        | 0x3400..=0x4DBF   // CJK 扩展A
+        | 0x4E00..=0x9FFF   // CJK 统一汉字
+        | 0xAC00..=0xD7AF   // 韩文音节
+        | 0xF900..=0xFAFF   // CJK 兼容汉字
+    )
+}
+
+/// 分词
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    let flush_word = |word: &mut String, tokens: &mut Vec<String>| {
+        if !word.is_empty() {
+            tokens.push(std::mem::take(word));
+        }
+    };
+    let flush_cjk = |run: &mut Vec<char>, tokens: &mut Vec<String>| {
+        for (i, c) in run.iter().enumerate() {
+            tokens.push(c.to_string());
+            if let Some(next) = run.get(i + 1) {
+                tokens.push(format!("{}{}", c, next));
+            }
+        }
+        run.clear();
+    };
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut tokens);
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() || c == '_' {
+            flush_cjk(&mut cjk_run, &mut tokens);
+            word.extend(c.to_lowercase());
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_cjk(&mut cjk_run, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_cjk(&mut cjk_run, &mut tokens);
+
+    tokens
+}
+
+/// 对记忆按与查询的相关度排序，只返回得分大于0的前 `limit` 条
+pub fn rank(entries: &[MemoryEntry], query: &str, limit: usize) -> Vec<SearchHit> {
+    let query_tokens = tokenize(query);
+    if query_tokens.is_empty() || entries.is_empty() {
+        return Vec::new();
+    }
+
+    // 标签参与检索，便于按标签召回
+    let documents: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| tokenize(&format!("{} {}", entry.content, entry.tags.join(" "))))
+        .collect();
+
+    let doc_count = documents.len() as f64;
+    let avg_len = documents.iter().map(|d| d.len()).sum::<usize>() as f64 / doc_count;
+
+    let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+    for doc in &documents {
+        let mut seen: Vec<&str> = doc.iter().map(String::as_str).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        for token in seen {
+            *doc_freq.entry(token).or_insert(0) += 1;
+        }
+    }
+
+    let mut query_terms: Vec<&str> = query_tokens.iter().map(String::as_str).collect();
+    query_terms.sort_unstable();
+    query_terms.dedup();
+
+    let mut hits: Vec<SearchHit> = documents
+        .iter()
+        .zip(entries)
+        .filter_map(|(doc, entry)| {
+            let mut term_freq: HashMap<&str, usize> = HashMap::new();
+            for token in doc {
+                *term_freq.entry(token.as_str()).or_insert(0) += 1;
+            }
+
+            let doc_len = doc.len() as f64;
+            let score: f64 = query_terms
+                .iter()
+                .filter_map(|term| {
+                    let tf = *term_freq.get(term)? as f64;
+                    let df = doc_freq.get(term).copied().unwrap_or(0) as f64;
+                    let idf = ((doc_count - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / avg_len.max(1.0));
+                    Some(idf * tf * (BM25_K1 + 1.0) / (tf + norm))
+                })
+                .sum();
+
+            (score > 0.0).then(|| SearchHit { entry: entry.clone(), score })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(limit);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::entry;
+
+    #[test]
+    fn test_tokenize_mixed_text() {
+        let tokens = tokenize("使用pnpm安装");
+        assert!(tokens.contains(&"pnpm".to_string()));
+        assert!(tokens.contains(&"使用".to_string()));
+        assert!(tokens.contains(&"安装".to_string()));
+        assert!(tokens.contains(&"装".to_string()));
+    }
+
+    #[test]
+    fn test_rank_prefers_relevant_entries() {
+        let entries = vec![
+            entry("前端组件使用 Vue 3 组合式 API").id("a").build(),
+            entry("数据库迁移前先备份").id("b").build(),
+            entry("提交信息使用中文").id("c").build(),
+        ];
+
+        let hits = rank(&entries, "数据库备份", 5);
+        assert_eq!(hits[0].entry.id, "b");
+
+        let hits = rank(&entries, "vue", 5);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.id, "a");
+    }
+}
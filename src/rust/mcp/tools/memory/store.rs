@@ -0,0 +1,114 @@
+//! 记忆文件的跨进程写入保护
+//!
+//! 多个智能体可能同时写同一个记忆目录。读改写期间持有目录级的建议锁，
+//! 文件先写入同目录下的临时文件再重命名替换，读取方不会看到写了一半的文件
+
+use anyhow::Result;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::layout::STATE_FILE;
+
+/// 锁文件名
+pub const LOCK_FILE: &str = ".lock";
+
+/// 记忆目录下不应提交到仓库的文件
+const GITIGNORE_LINES: &[&str] = &[LOCK_FILE, "*.tmp", STATE_FILE];
+
+/// 变更历史只追加，两个分支各自追加的记录按行合并即可，不需要手动解决冲突
+const GITATTRIBUTES_LINES: &[&str] = &["history.jsonl merge=union"];
+
+/// 等待锁的最长时间
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 目录锁，离开作用域时释放
+pub struct DirLock {
+    _file: File,
+}
+
+/// 获取记忆目录的独占锁，其他进程持有锁时等待
+pub fn lock_dir(dir: &Path) -> Result<DirLock> {
+    let path = dir.join(LOCK_FILE);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(|e| anyhow::anyhow!("无法创建记忆锁文件: {}\n错误: {}", path.display(), e))?;
+
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(DirLock { _file: file }),
+            Err(TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Err(TryLockError::WouldBlock) => {
+                return Err(anyhow::anyhow!(
+                    "记忆目录被其他进程占用超过 {} 秒: {}",
+                    LOCK_TIMEOUT.as_secs(),
+                    dir.display()
+                ));
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(anyhow::anyhow!("无法锁定记忆目录: {}\n错误: {}", dir.display(), e));
+            }
+        }
+    }
+}
+
+/// 原子写入：先写临时文件并落盘，再重命名覆盖目标文件
+pub fn atomic_write(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    let filename = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("无效的文件路径: {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", filename, uuid::Uuid::new_v4().simple()));
+
+    let result = (|| -> std::io::Result<()> {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(content.as_ref())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(anyhow::anyhow!("写入记忆文件失败: {}\n错误: {}", path.display(), e));
+    }
+    Ok(())
+}
+
+/// 在记忆目录中写入 .gitignore，避免锁文件、临时文件和本地状态被提交
+pub fn ensure_gitignore(dir: &Path) -> Result<()> {
+    ensure_lines(&dir.join(".gitignore"), GITIGNORE_LINES)
+}
+
+/// 在记忆目录中写入 .gitattributes，让变更历史在合并时自动按行合并
+pub fn ensure_gitattributes(dir: &Path) -> Result<()> {
+    ensure_lines(&dir.join(".gitattributes"), GITATTRIBUTES_LINES)
+}
+
+/// 在文件末尾补上缺少的行，已有的内容保持不变
+fn ensure_lines(path: &Path, lines: &[&str]) -> Result<()> {
+    let mut content = fs::read_to_string(path).unwrap_or_default();
+    let missing: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| !content.lines().any(|existing| existing.trim() == *line))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for line in missing {
+        content.push_str(line);
+        content.push('\n');
+    }
+    atomic_write(path, content)
+}
@@ -0,0 +1,110 @@
+//! 记忆模块测试共用的条目构造和临时目录
+
+use chrono::{DateTime, Duration, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::location::ProjectStorage;
+use super::manager::MemoryManager;
+use super::types::{MemoryCategory, MemoryEntry, MemoryScope};
+
+/// 测试条目构造器，默认以内容作为ID、属于项目作用域的规范，创建和更新时间为当前时间
+pub struct EntryBuilder(MemoryEntry);
+
+pub fn entry(content: &str) -> EntryBuilder {
+    let now = Utc::now();
+    EntryBuilder(MemoryEntry {
+        id: content.to_string(),
+        content: content.to_string(),
+        category: MemoryCategory::RULE,
+        created_at: now,
+        updated_at: now,
+        tags: Vec::new(),
+        scope: MemoryScope::Project,
+        branch: None,
+        expires_at: None,
+    })
+}
+
+impl EntryBuilder {
+    pub fn id(mut self, id: &str) -> Self {
+        self.0.id = id.to_string();
+        self
+    }
+
+    pub fn category(mut self, category: MemoryCategory) -> Self {
+        self.0.category = category;
+        self
+    }
+
+    pub fn scope(mut self, scope: MemoryScope) -> Self {
+        self.0.scope = scope;
+        self
+    }
+
+    /// 同时设置创建和更新时间
+    pub fn at(mut self, time: DateTime<Utc>) -> Self {
+        self.0.created_at = time;
+        self.0.updated_at = time;
+        self
+    }
+
+    /// 创建和更新时间设为若干天前
+    pub fn age_days(self, days: i64) -> Self {
+        self.at(Utc::now() - Duration::days(days))
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.0.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        self
+    }
+
+    pub fn branch(mut self, branch: &str) -> Self {
+        self.0.branch = Some(branch.to_string());
+        self
+    }
+
+    pub fn expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.0.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn build(self) -> MemoryEntry {
+        self.0
+    }
+}
+
+/// 系统临时目录下的唯一目录，离开作用域时连同内容一起删除
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// 只生成路径，目录由测试按需创建
+    pub fn new(label: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("cunzhi-{}-{}", label, uuid::Uuid::new_v4().simple()));
+        Self { path }
+    }
+
+    /// 带 .git 目录的临时项目，可直接打开项目记忆
+    pub fn project(label: &str) -> Self {
+        let dir = Self::new(label);
+        fs::create_dir_all(dir.path.join(".git")).unwrap();
+        dir
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 以仓库内存储打开该项目的记忆
+    pub fn manager(&self) -> MemoryManager {
+        MemoryManager::with_storage(&self.path.to_string_lossy(), ProjectStorage::Repository).unwrap()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -1,5 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+
+use super::layout::MemoryLayout;
+use crate::i18n::{t, Msg};
 
 /// 记忆条目结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,22 +14,129 @@ pub struct MemoryEntry {
     pub category: MemoryCategory,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 所属作用域，由存储位置决定，不写入记忆文件
+    #[serde(default)]
+    pub scope: MemoryScope,
+    /// 限定生效的 git 分支，为空时对所有分支生效
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// 过期时间，为空时永不过期
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl MemoryEntry {
+    /// 在给定分支上是否可见，未限定分支的条目始终可见
+    pub fn visible_on(&self, branch: Option<&str>) -> bool {
+        match &self.branch {
+            None => true,
+            Some(own) => Some(own.as_str()) == branch,
+        }
+    }
+
+    /// 在给定时间是否已过期
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+
+    /// 是否包含全部给定标签，忽略大小写
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.iter().any(|own| own.eq_ignore_ascii_case(tag.trim())))
+    }
+}
+
+/// 记忆分类标识
+///
+/// 内置 rule、preference、pattern、context 四个分类，其余分类在配置或项目的 categories.json 中声明，
+/// 标题、文件名和召回优先级由 [`CategoryRegistry`](super::category::CategoryRegistry) 提供
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MemoryCategory(Cow<'static, str>);
+
+impl MemoryCategory {
+    pub const RULE: MemoryCategory = MemoryCategory(Cow::Borrowed("rule")); // 开发规范和规则
+    pub const PREFERENCE: MemoryCategory = MemoryCategory(Cow::Borrowed("preference")); // 用户偏好设置
+    pub const PATTERN: MemoryCategory = MemoryCategory(Cow::Borrowed("pattern")); // 常用模式和最佳实践
+    pub const CONTEXT: MemoryCategory = MemoryCategory(Cow::Borrowed("context")); // 项目上下文信息
+
+    /// 由标识创建分类，统一为小写
+    pub fn new(key: &str) -> Self {
+        MemoryCategory(Cow::Owned(key.trim().to_lowercase()))
+    }
+
+    /// 分类在工具参数中使用的标识
+    pub fn key(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for MemoryCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// 记忆作用域，召回时按 项目 > 团队 > 全局 的优先级合并
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryScope {
+    /// 当前项目
+    #[default]
+    Project,
+    /// 团队共享目录
+    Team,
+    /// 用户全局
+    Global,
+}
+
+impl MemoryScope {
+    /// 按优先级从高到低排列的所有作用域
+    pub const ALL: [MemoryScope; 3] = [MemoryScope::Project, MemoryScope::Team, MemoryScope::Global];
+
+    /// 作用域在工具参数中使用的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            MemoryScope::Project => "project",
+            MemoryScope::Team => "team",
+            MemoryScope::Global => "global",
+        }
+    }
+
+    /// 作用域显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            MemoryScope::Project => t(Msg::JiScopeProject),
+            MemoryScope::Team => t(Msg::JiScopeTeam),
+            MemoryScope::Global => t(Msg::JiScopeGlobal),
+        }
+    }
 }
 
-/// 记忆分类
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-pub enum MemoryCategory {
-    Rule,        // 开发规范和规则
-    Preference,  // 用户偏好设置
-    Pattern,     // 常用模式和最佳实践
-    Context,     // 项目上下文信息
+/// 添加记忆时遇到相似条目的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    /// 拒绝添加，返回已有条目
+    #[default]
+    Reject,
+    /// 与已有条目合并
+    Merge,
+    /// 忽略相似性直接添加
+    Allow,
 }
 
 /// 记忆元数据
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MemoryMetadata {
     pub project_path: String,
-    pub last_organized: DateTime<Utc>,
+    /// 最近一次执行整理的时间，从未整理时为空
+    #[serde(default)]
+    pub last_organized: Option<DateTime<Utc>>,
     pub total_entries: usize,
     pub version: String,
+    /// 记忆目录布局
+    #[serde(default)]
+    pub layout: MemoryLayout,
 }
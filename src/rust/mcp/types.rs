@@ -1,6 +1,8 @@
 use chrono;
 use serde::{Deserialize, Serialize};
 
+use super::tools::memory::{DuplicatePolicy, MemoryScope};
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct ZhiRequest {
     #[schemars(description = "要显示给用户的消息")]
@@ -11,26 +13,108 @@ pub struct ZhiRequest {
     #[schemars(description = "消息是否为Markdown格式，默认为true")]
     #[serde(default = "default_is_markdown")]
     pub is_markdown: bool,
+    #[schemars(description = "当前项目路径（可选），用于匹配自动回复策略")]
+    #[serde(default)]
+    pub project_path: Option<String>,
 }
 
 fn default_is_markdown() -> bool {
     true
 }
 
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
+/// 记忆工具参数，参数说明见 server.rs 中手写的工具 schema
+#[derive(Debug, Deserialize)]
 pub struct JiyiRequest {
-    #[schemars(description = "操作类型：记忆(添加记忆), 回忆(获取项目信息)")]
-    pub action: String,
-    #[schemars(description = "项目路径（必需）")]
+    pub action: JiyiAction,
     pub project_path: String,
-    #[schemars(description = "记忆内容（记忆操作时必需）")]
     #[serde(default)]
     pub content: String,
-    #[schemars(
-        description = "记忆分类：rule(规范规则), preference(用户偏好), pattern(最佳实践), context(项目上下文)"
-    )]
-    #[serde(default = "default_category")]
-    pub category: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub on_duplicate: DuplicatePolicy,
+    #[serde(default)]
+    pub scope: Option<MemoryScope>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub plan_id: String,
+    #[serde(default)]
+    pub rules_file: Option<String>,
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub ttl: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+/// 记忆工具操作类型，同时接受中文名和英文别名
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JiyiAction {
+    Add,
+    Recall,
+    List,
+    Update,
+    Delete,
+    Move,
+    Search,
+    Organize,
+    Import,
+    Export,
+    Promote,
+    History,
+    Undo,
+}
+
+impl JiyiAction {
+    /// 操作与其中文名、英文别名的对应表，反序列化和工具 schema 都由此生成
+    const NAMES: &'static [(JiyiAction, &'static str, &'static str)] = &[
+        (JiyiAction::Add, "记忆", "add"),
+        (JiyiAction::Recall, "回忆", "recall"),
+        (JiyiAction::List, "列表", "list"),
+        (JiyiAction::Update, "更新", "update"),
+        (JiyiAction::Delete, "删除", "delete"),
+        (JiyiAction::Move, "移动", "move"),
+        (JiyiAction::Search, "搜索", "search"),
+        (JiyiAction::Organize, "整理", "organize"),
+        (JiyiAction::Import, "导入", "import"),
+        (JiyiAction::Export, "导出", "export"),
+        (JiyiAction::Promote, "提升", "promote"),
+        (JiyiAction::History, "历史", "history"),
+        (JiyiAction::Undo, "撤销", "undo"),
+    ];
+
+    /// 所有可接受的操作名（含英文别名），用于生成工具 schema
+    pub fn accepted_names() -> Vec<&'static str> {
+        Self::NAMES.iter().flat_map(|(_, name, alias)| [*name, *alias]).collect()
+    }
+
+    /// 按中文名或英文别名查找操作
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(_, zh, alias)| *zh == name || *alias == name)
+            .map(|(action, _, _)| *action)
+    }
+}
+
+impl<'de> Deserialize<'de> for JiyiAction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::from_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("未知的操作 `{}`，可选: {}", name, Self::accepted_names().join(", "))))
+    }
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -41,16 +125,24 @@ pub struct AcemcpRequest {
     pub query: String,
 }
 
-fn default_category() -> String {
-    "context".to_string()
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PopupRequest {
     pub id: String,
     pub message: String,
     pub predefined_options: Option<Vec<String>>,
     pub is_markdown: bool,
+    #[serde(default)]
+    pub project_path: Option<String>,
+    #[serde(default)]
+    pub route: Option<RouteDecision>,
+}
+
+/// 交互路由决策，由寸止进程在发起弹窗前计算
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteDecision {
+    pub backend: String, // "popup" | "telegram" | "both"
+    #[serde(default)]
+    pub mute_audio: bool,
 }
 
 /// 新的结构化响应数据格式
@@ -139,3 +231,20 @@ pub fn build_continue_response(request_id: Option<String>, source: &str) -> Stri
     let response = build_mcp_response(Some(continue_prompt), vec![], vec![], request_id, source);
     response.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_accepted_action_name_deserializes() {
+        for (action, name, alias) in JiyiAction::NAMES {
+            for value in [name, alias] {
+                let parsed: JiyiAction = serde_json::from_value(serde_json::json!(value)).unwrap();
+                assert_eq!(parsed, *action, "{}", value);
+            }
+        }
+        assert_eq!(JiyiAction::accepted_names().len(), JiyiAction::NAMES.len() * 2);
+        assert!(serde_json::from_value::<JiyiAction>(serde_json::json!("unknown")).is_err());
+    }
+}
@@ -4,28 +4,30 @@
 
 use rmcp::Error as McpError;
 
+use crate::i18n::{tf, Msg};
+
 /// MCP 错误类型枚举
 #[derive(Debug, thiserror::Error)]
 pub enum McpToolError {
-    #[error("项目路径错误: {0}")]
+    #[error("{}", tf(Msg::ErrProjectPath, &[.0]))]
     ProjectPath(String),
     
-    #[error("弹窗创建失败: {0}")]
+    #[error("{}", tf(Msg::ErrPopupCreation, &[.0]))]
     PopupCreation(String),
     
-    #[error("响应解析失败: {0}")]
+    #[error("{}", tf(Msg::ErrResponseParsing, &[.0]))]
     ResponseParsing(String),
     
-    #[error("记忆管理错误: {0}")]
+    #[error("{}", tf(Msg::ErrMemory, &[.0]))]
     Memory(String),
     
-    #[error("IO 错误: {0}")]
+    #[error("{}", tf(Msg::ErrIo, &[.0]))]
     Io(#[from] std::io::Error),
     
-    #[error("JSON 序列化错误: {0}")]
+    #[error("{}", tf(Msg::ErrJson, &[.0]))]
     Json(#[from] serde_json::Error),
     
-    #[error("通用错误: {0}")]
+    #[error("{}", tf(Msg::ErrGeneric, &[.0]))]
     Generic(#[from] anyhow::Error),
 }
 
@@ -41,10 +43,10 @@ impl From<McpToolError> for McpError {
                 McpError::internal_error(msg, None)
             }
             McpToolError::Io(e) => {
-                McpError::internal_error(format!("IO 错误: {}", e), None)
+                McpError::internal_error(tf(Msg::ErrIo, &[&e]), None)
             }
             McpToolError::Json(e) => {
-                McpError::internal_error(format!("JSON 错误: {}", e), None)
+                McpError::internal_error(tf(Msg::ErrJson, &[&e]), None)
             }
             McpToolError::Generic(e) => {
                 McpError::internal_error(e.to_string(), None)
@@ -3,6 +3,8 @@ use crate::constants::telegram as telegram_constants;
 use crate::telegram::{
     handle_callback_query, handle_text_message, CallbackQueryResult, TelegramCore,
 };
+use crate::mcp::handlers::{enhance_with_llm, is_llm_mode, load_enhance_config, render_enhance_prompt, EnhanceContext};
+use crate::i18n::{tf, Msg};
 use crate::log_important;
 use tauri::{AppHandle, Emitter, Manager, State};
 use teloxide::prelude::*;
@@ -178,6 +180,7 @@ pub async fn start_telegram_sync(
     message: String,
     predefined_options: Vec<String>,
     is_markdown: bool,
+    project_path: Option<String>,
     state: State<'_, AppState>,
     app_handle: AppHandle,
 ) -> Result<(), String> {
@@ -248,6 +251,7 @@ pub async fn start_telegram_sync(
             chat_id_clone,
             app_handle_clone,
             predefined_options,
+            project_path,
         )
         .await
         {
@@ -265,6 +269,7 @@ async fn start_telegram_listener(
     chat_id: String,
     app_handle: AppHandle,
     predefined_options_list: Vec<String>,
+    project_path: Option<String>,
 ) -> Result<(), String> {
     // 从AppHandle获取应用状态来读取API URL配置
     let api_url = match app_handle.try_state::<AppState>() {
@@ -360,27 +365,43 @@ async fn start_telegram_listener(
                                     }
                                     CallbackQueryResult::EnhancePressed => {
                                         // 增强按钮点击 - 参照发送和继续按钮的实现
-                                        // 构建增强 prompt
-                                        let enhance_prompt = format!(
-                                            r#"Use the following prompt to optimize and enhance the context of the content in 《》, and return the enhanced result by calling the tool '寸止' after completion.Here is an instruction that I'd like to give you, but it needs to be improved. Rewrite and enhance this instruction to make it clearer, more specific, less ambiguous, and correct any mistakes. Reply immediately with your answer, even if you're not sure. Consider the context of our conversation history when enhancing the prompt. Reply with the following format:
-
-### BEGIN RESPONSE ###
-Here is an enhanced version of the original instruction that is more specific and clear:
-<augment-enhanced-prompt>enhanced prompt goes here</augment-enhanced-prompt>
-
-### END RESPONSE ###
-
-Here is my original instruction:
-
-《{}》"#,
-                                            user_input
-                                        );
-                                        
-                                        // 发送确认消息
-                                        let _ = core.send_message(&format!("✨ 增强请求已发送\n\n📝 原文：{}", user_input)).await;
-                                        
-                                        // 发送增强事件到前端，携带构建好的 prompt
-                                        let _ = app_handle.emit("telegram-event", &TelegramEvent::EnhancePressed { text: enhance_prompt });
+                                        let enhance_config = app_handle
+                                            .try_state::<AppState>()
+                                            .and_then(|state| {
+                                                state.config.lock().ok().map(|config| config.enhance_config.clone())
+                                            })
+                                            .unwrap_or_else(load_enhance_config);
+                                        let selected_vec: Vec<String> =
+                                            selected_options.iter().cloned().collect();
+                                        let context = EnhanceContext {
+                                            original_text: &user_input,
+                                            selected_options: &selected_vec,
+                                            project_path: project_path.as_deref(),
+                                        };
+
+                                        if is_llm_mode(&enhance_config) {
+                                            // 模型直接改写，结果回填到输入框，由用户确认后发送
+                                            match enhance_with_llm(&enhance_config, &context).await {
+                                                Ok(enhanced) => {
+                                                    let _ = core.send_message(&tf(Msg::TgEnhanceResult, &[&enhanced])).await;
+                                                    user_input = enhanced.clone();
+                                                    let _ = app_handle.emit("telegram-event", &TelegramEvent::TextUpdated { text: enhanced });
+                                                }
+                                                Err(e) => {
+                                                    log_important!(warn, "调用增强模型失败: {}", e);
+                                                    let _ = core.send_message(&tf(Msg::TgEnhanceFailed, &[&e])).await;
+                                                }
+                                            }
+                                        } else {
+                                            // 按配置的增强模板构建 prompt
+                                            let enhance_prompt = render_enhance_prompt(&enhance_config, &context);
+
+                                            // 发送确认消息
+                                            let _ = core.send_message(&tf(Msg::TgEnhanceSentWithText, &[&user_input])).await;
+
+                                            // 发送增强事件到前端，携带构建好的 prompt
+                                            let _ = app_handle.emit("telegram-event", &TelegramEvent::EnhancePressed { text: enhance_prompt });
+                                        }
                                     }
                                     CallbackQueryResult::ContinuePressed => {
                                         // 继续按钮点击
@@ -11,6 +11,7 @@ use teloxide::{
 };
 
 use super::markdown::process_telegram_markdown;
+use crate::i18n::{t, Msg};
 
 /// Telegram事件类型
 #[derive(Debug, Clone, Serialize)]
@@ -142,7 +143,7 @@ impl TelegramCore {
         let inline_keyboard = Self::create_operation_inline_keyboard(continue_reply_enabled);
 
         // 发送操作消息
-        let operation_message = "👇 点击按钮完成操作";
+        let operation_message = t(Msg::TgOperationPrompt);
 
         match self
             .bot
@@ -211,12 +212,12 @@ impl TelegramCore {
         let mut buttons = Vec::new();
         
         // 增强按钮
-        buttons.push(InlineKeyboardButton::callback("✨ 增强", "operation:enhance"));
+        buttons.push(InlineKeyboardButton::callback(t(Msg::TgButtonEnhance), "operation:enhance"));
         
         if continue_reply_enabled {
-            buttons.push(InlineKeyboardButton::callback("⏩ 继续", "operation:continue"));
+            buttons.push(InlineKeyboardButton::callback(t(Msg::TgButtonContinue), "operation:continue"));
         }
-        buttons.push(InlineKeyboardButton::callback("↗️ 发送", "operation:send"));
+        buttons.push(InlineKeyboardButton::callback(t(Msg::TgButtonSend), "operation:send"));
         
         InlineKeyboardMarkup::new(vec![buttons])
     }
@@ -339,13 +340,13 @@ pub fn build_feedback_message(
             "请按照最佳实践继续".to_string()
         };
 
-        format!("✅ 发送成功！\n\n📝 选中的选项：\n• ⏩ {}", continue_prompt)
+        format!("{}\n\n{}\n• ⏩ {}", t(Msg::TgSendSuccess), t(Msg::TgSelectedOptions), continue_prompt)
     } else {
         // 发送操作的反馈消息
-        let mut feedback_message = "✅ 发送成功！\n\n📝 选中的选项：\n".to_string();
+        let mut feedback_message = format!("{}\n\n{}\n", t(Msg::TgSendSuccess), t(Msg::TgSelectedOptions));
 
         if selected_options.is_empty() {
-            feedback_message.push_str("• 无");
+            feedback_message.push_str(&format!("• {}", t(Msg::TgNone)));
         } else {
             for opt in selected_options {
                 feedback_message.push_str(&format!("• {}\n", opt));
@@ -353,7 +354,7 @@ pub fn build_feedback_message(
         }
 
         if !user_input.is_empty() {
-            feedback_message.push_str(&format!("\n📝 补充说明：\n{}", user_input));
+            feedback_message.push_str(&format!("\n{}\n{}", t(Msg::TgExtraInput), user_input));
         }
 
         feedback_message
@@ -395,11 +396,10 @@ pub async fn test_telegram_connection_with_api_url(
         .map_err(|_| anyhow::anyhow!("Chat ID格式无效，请输入有效的数字ID"))?;
 
     // 发送测试消息
-    let test_message =
-        "🤖 寸止应用测试消息\n\n这是一条来自寸止应用的测试消息，表示Telegram Bot配置成功！";
+    let test_message = t(Msg::TgTestMessage);
 
     match bot.send_message(ChatId(chat_id_parsed), test_message).await {
-        Ok(_) => Ok("测试消息发送成功！Telegram Bot配置正确。".to_string()),
+        Ok(_) => Ok(t(Msg::TgTestSuccess).to_string()),
         Err(e) => Err(anyhow::anyhow!("发送测试消息失败: {}", e)),
     }
 }
@@ -3,8 +3,10 @@ use std::collections::HashSet;
 use teloxide::prelude::*;
 
 use crate::config::load_standalone_config;
+use crate::mcp::handlers::{enhance_with_llm, is_llm_mode, load_enhance_config, render_enhance_prompt, EnhanceContext};
 use crate::mcp::types::{build_continue_response, build_send_response, PopupRequest};
 use crate::telegram::{handle_callback_query, handle_text_message, CallbackQueryResult, TelegramCore, TelegramEvent};
+use crate::i18n::{t, tf, Msg};
 use crate::log_important;
 
 /// 处理纯Telegram模式的MCP请求（不启动GUI）
@@ -90,7 +92,7 @@ async fn start_telegram_mcp_listener(
                                 &predefined_options,
                                 &mut selected_options,
                                 &mut options_message_id,
-                                &user_input,
+                                &mut user_input,
                                 &request,
                             ).await {
                                 if let Some(_result) = e.downcast_ref::<ProcessingComplete>() {
@@ -137,7 +139,7 @@ async fn handle_callback_query_update(
     predefined_options: &[String],
     selected_options: &mut HashSet<String>,
     options_message_id: &mut Option<i32>,
-    user_input: &str,
+    user_input: &mut String,
     request: &PopupRequest,
 ) -> Result<()> {
     // 今callback_query中提取消息ID
@@ -170,8 +172,10 @@ async fn handle_callback_query_update(
             }
             CallbackQueryResult::EnhancePressed => {
                 // 增强按钮点击
-                handle_enhance_pressed(core, user_input, request).await?;
-                return Err(ProcessingComplete.into());
+                let selected_vec: Vec<String> = selected_options.iter().cloned().collect();
+                if handle_enhance_pressed(core, user_input, &selected_vec, request).await? {
+                    return Err(ProcessingComplete.into());
+                }
             }
             CallbackQueryResult::ContinuePressed => {
                 // 继续按钮点击
@@ -322,26 +326,40 @@ async fn handle_continue_pressed(
 }
 
 /// 处理增强按钮按下
+///
+/// 返回 true 表示增强请求已发回给AI，请求处理结束；
+/// 模型直接改写时返回 false，改写结果作为新的输入等待用户确认发送
 async fn handle_enhance_pressed(
     core: &TelegramCore,
-    user_input: &str,
+    user_input: &mut String,
+    selected_options: &[String],
     request: &PopupRequest,
-) -> Result<()> {
-    // 构建增强prompt
-    let enhance_prompt = format!(
-        r#"Use the following prompt to optimize and enhance the context of the content in 《》, and return the enhanced result by calling the tool '寸止' after completion.Here is an instruction that I'd like to give you, but it needs to be improved. Rewrite and enhance this instruction to make it clearer, more specific, less ambiguous, and correct any mistakes. Reply immediately with your answer, even if you're not sure. Consider the context of our conversation history when enhancing the prompt. Reply with the following format:
-
-### BEGIN RESPONSE ###
-Here is an enhanced version of the original instruction that is more specific and clear:
-<augment-enhanced-prompt>enhanced prompt goes here</augment-enhanced-prompt>
-
-### END RESPONSE ###
+) -> Result<bool> {
+    let enhance_config = load_enhance_config();
+    let context = EnhanceContext {
+        original_text: user_input,
+        selected_options,
+        project_path: request.project_path.as_deref(),
+    };
 
-Here is my original instruction:
+    if is_llm_mode(&enhance_config) {
+        match enhance_with_llm(&enhance_config, &context).await {
+            Ok(enhanced) => {
+                let _ = core
+                    .send_message(&tf(Msg::TgEnhanceResult, &[&enhanced]))
+                    .await;
+                *user_input = enhanced;
+            }
+            Err(e) => {
+                log_important!(warn, "调用增强模型失败: {}", e);
+                let _ = core.send_message(&tf(Msg::TgEnhanceFailed, &[&e])).await;
+            }
+        }
+        return Ok(false);
+    }
 
-《{}》"#,
-        user_input
-    );
+    // 按配置的增强模板构建prompt
+    let enhance_prompt = render_enhance_prompt(&enhance_config, &context);
 
     // 构建响应
     let response = build_send_response(
@@ -356,9 +374,9 @@ Here is my original instruction:
     println!("{}", response);
 
     // 发送确认消息
-    let _ = core.send_message("✨ 增强请求已发送，等待 AI 处理...").await;
+    let _ = core.send_message(t(Msg::TgEnhanceSent)).await;
 
-    Ok(())
+    Ok(true)
 }
 
 /// 处理完成标记（用于从监听循环中退出）
@@ -1,7 +1,9 @@
-use crate::config::{save_config, load_config, AppState, ReplyConfig, WindowConfig, CustomPrompt, CustomPromptConfig, ShortcutConfig, ShortcutBinding};
+use crate::config::{save_config, load_config, AppState, ReplyConfig, PolicyAction, PolicyConfig, RoutingConfig, EnhanceConfig, MemoryConfig, WindowConfig, CustomPrompt, CustomPromptConfig, ShortcutConfig, ShortcutBinding, ImportReport, ImportStrategy, PackFormat, apply_pack, export_pack, parse_pack};
 use crate::constants::{window, ui, validation};
+use crate::i18n::{self, Locale};
 use crate::mcp::types::{build_continue_response, build_send_response, ImageAttachment, PopupRequest};
-use crate::mcp::handlers::create_tauri_popup;
+use crate::mcp::tools::memory::{self, agent_rules, AgentRulesFile, CategoryRegistry, ChangeSource, MemoryLayout, MemoryManager, MigrationReport, OrganizeReport, ProjectStorage, RulesExportReport, RulesImportReport, SecretPolicy};
+use crate::mcp::handlers::{create_tauri_popup, enhance_with_llm, EnhanceContext, BACKEND_BOTH, BACKEND_POPUP, BACKEND_TELEGRAM};
 use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
@@ -185,6 +187,361 @@ pub async fn set_reply_config(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_locale(state: State<'_, AppState>) -> Result<String, String> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|e| format!("获取配置失败: {}", e))?;
+    Ok(config.locale.clone())
+}
+
+#[tauri::command]
+pub async fn set_locale(
+    locale: String,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    if !Locale::is_supported(&locale) {
+        return Err(format!("不支持的语言: {}", locale));
+    }
+
+    {
+        let mut config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.locale = locale.clone();
+    }
+
+    // 保存配置到文件
+    save_config(&state, &app)
+        .await
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    i18n::set_locale(Locale::from_code(&locale));
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_policy_config(state: State<'_, AppState>) -> Result<PolicyConfig, String> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|e| format!("获取配置失败: {}", e))?;
+    Ok(config.policy_config.clone())
+}
+
+#[tauri::command]
+pub async fn set_policy_config(
+    policy_config: PolicyConfig,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    // 提前校验正则和动作，避免保存后规则静默失效
+    for rule in &policy_config.rules {
+        if let Some(pattern) = &rule.message_pattern {
+            regex::Regex::new(pattern)
+                .map_err(|e| format!("规则 {} 的正则无效: {}", rule.name, e))?;
+        }
+        match rule.action {
+            PolicyAction::Unknown => return Err(format!("规则 {} 的动作无效", rule.name)),
+            PolicyAction::SelectOption if rule.option.as_deref().map(str::trim).unwrap_or_default().is_empty() => {
+                return Err(format!("规则 {} 需要指定自动选择的选项", rule.name));
+            }
+            _ => {}
+        }
+    }
+
+    {
+        let mut config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.policy_config = policy_config;
+    }
+
+    // 保存配置到文件
+    save_config(&state, &app)
+        .await
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_routing_config(state: State<'_, AppState>) -> Result<RoutingConfig, String> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|e| format!("获取配置失败: {}", e))?;
+    Ok(config.routing_config.clone())
+}
+
+#[tauri::command]
+pub async fn set_routing_config(
+    routing_config: RoutingConfig,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    for schedule in &routing_config.schedules {
+        for time in [&schedule.time_start, &schedule.time_end].into_iter().flatten() {
+            chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|_| format!("时间段 {} 的时间格式无效: {}，应为 HH:MM", schedule.name, time))?;
+        }
+        if let Some(day) = schedule.weekdays.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(format!("时间段 {} 的星期无效: {}，应为 1-7", schedule.name, day));
+        }
+        if let Some(backend) = &schedule.backend {
+            if ![BACKEND_POPUP, BACKEND_TELEGRAM, BACKEND_BOTH].contains(&backend.as_str()) {
+                return Err(format!("时间段 {} 的交互方式无效: {}", schedule.name, backend));
+            }
+        }
+    }
+
+    {
+        let mut config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.routing_config = routing_config;
+    }
+
+    // 保存配置到文件
+    save_config(&state, &app)
+        .await
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_enhance_config(state: State<'_, AppState>) -> Result<EnhanceConfig, String> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|e| format!("获取配置失败: {}", e))?;
+    Ok(config.enhance_config.clone())
+}
+
+#[tauri::command]
+pub async fn set_enhance_config(
+    enhance_config: EnhanceConfig,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    if !enhance_config
+        .templates
+        .iter()
+        .any(|t| t.id == enhance_config.active_template)
+    {
+        return Err(format!("增强模板不存在: {}", enhance_config.active_template));
+    }
+
+    {
+        let mut config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.enhance_config = enhance_config;
+    }
+
+    // 保存配置到文件
+    save_config(&state, &app)
+        .await
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_memory_config(state: State<'_, AppState>) -> Result<MemoryConfig, String> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|e| format!("获取配置失败: {}", e))?;
+    Ok(config.memory_config.clone())
+}
+
+#[tauri::command]
+pub async fn set_memory_config(
+    memory_config: MemoryConfig,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let team_dir = memory_config.team_dir.trim();
+    if !team_dir.is_empty() && !std::path::Path::new(team_dir).is_absolute() {
+        return Err(format!("团队记忆目录必须是绝对路径: {}", team_dir));
+    }
+    if ProjectStorage::from_key(&memory_config.storage).is_none() {
+        return Err(format!("无效的记忆存储位置: {}", memory_config.storage));
+    }
+    if SecretPolicy::from_key(&memory_config.secret_policy).is_none() {
+        return Err(format!("无效的密钥处理方式: {}", memory_config.secret_policy));
+    }
+    let mut categories = CategoryRegistry::builtin();
+    for def in &memory_config.categories {
+        categories.validate(def).map_err(|e| e.to_string())?;
+        categories.extend([def.clone()]);
+    }
+
+    {
+        let mut config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.memory_config = memory_config;
+    }
+
+    // 保存配置到文件
+    save_config(&state, &app)
+        .await
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    Ok(())
+}
+
+/// 在仓库内和应用数据目录之间迁移项目记忆
+///
+/// 新位置只为该项目登记，全局的存储位置配置只决定尚未使用过记忆的项目存放在哪里，其他项目不受影响
+#[tauri::command]
+pub async fn migrate_project_memory(project_path: String, target: String) -> Result<MigrationReport, String> {
+    let target_storage = ProjectStorage::from_key(&target)
+        .ok_or_else(|| format!("无效的记忆存储位置: {}", target))?;
+    let git_root = MemoryManager::resolve_git_root(&project_path).map_err(|e| e.to_string())?;
+
+    memory::migrate_project_memory(&git_root, target_storage).map_err(|e| format!("迁移记忆失败: {}", e))
+}
+
+/// 转换项目记忆目录的布局，返回转换的条目数
+///
+/// `entry` 为每条记忆一个文件，适合团队提交记忆目录；`category` 为每个分类一个文件
+#[tauri::command]
+pub async fn convert_memory_layout(project_path: String, layout: String) -> Result<usize, String> {
+    let layout = MemoryLayout::from_key(&layout).ok_or_else(|| format!("无效的记忆布局: {}，可选 category、entry", layout))?;
+    let mut manager = MemoryManager::new(&project_path).map_err(|e| e.to_string())?;
+    manager
+        .convert_layout(layout)
+        .map_err(|e| format!("转换记忆布局失败: {}", e))
+}
+
+/// 整理项目记忆，不带计划ID时只返回预览，带上预览返回的计划ID才会改写文件
+#[tauri::command]
+pub async fn organize_memory(
+    project_path: String,
+    plan_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<OrganizeReport, String> {
+    let archive_days = {
+        let config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.memory_config.archive_context_days
+    };
+
+    let manager = MemoryManager::new(&project_path).map_err(|e| e.to_string())?;
+    manager
+        .organize(archive_days, plan_id.as_deref().map(str::trim).filter(|id| !id.is_empty()))
+        .map_err(|e| format!("整理记忆失败: {}", e))
+}
+
+/// 解析规则文件标识，为空时使用项目中已存在的规则文件
+fn resolve_rules_files(project_root: &std::path::Path, files: &[String]) -> Result<Vec<AgentRulesFile>, String> {
+    if files.is_empty() {
+        return Ok(AgentRulesFile::existing(project_root));
+    }
+    files
+        .iter()
+        .map(|key| AgentRulesFile::from_key(key).ok_or_else(|| format!("未知的规则文件: {}", key)))
+        .collect()
+}
+
+/// 从 CLAUDE.md、AGENTS.md 等规则文件导入项目记忆
+#[tauri::command]
+pub async fn import_agent_rules(project_path: String, files: Vec<String>) -> Result<Vec<RulesImportReport>, String> {
+    let manager = MemoryManager::new(&project_path)
+        .map_err(|e| e.to_string())?
+        .with_source(ChangeSource::Gui);
+    let root = MemoryManager::resolve_git_root(&project_path).map_err(|e| e.to_string())?;
+
+    resolve_rules_files(&root, &files)?
+        .into_iter()
+        .map(|file| agent_rules::import_rules(&manager, &root, file).map_err(|e| format!("导入规则文件失败: {}", e)))
+        .collect()
+}
+
+/// 将项目记忆导出到规则文件的托管区块
+#[tauri::command]
+pub async fn export_agent_rules(project_path: String, files: Vec<String>) -> Result<Vec<RulesExportReport>, String> {
+    let manager = MemoryManager::new(&project_path).map_err(|e| e.to_string())?;
+    let root = MemoryManager::resolve_git_root(&project_path).map_err(|e| e.to_string())?;
+
+    let files = resolve_rules_files(&root, &files)?;
+    if files.is_empty() {
+        return Err("项目中没有找到规则文件，请指定要创建的文件".to_string());
+    }
+    files
+        .into_iter()
+        .map(|file| agent_rules::export_rules(&manager, &root, file).map_err(|e| format!("导出规则文件失败: {}", e)))
+        .collect()
+}
+
+/// 按当前增强模板渲染提示词，GUI与Telegram共用同一渲染逻辑
+#[tauri::command]
+pub async fn render_enhance_prompt(
+    original_text: String,
+    selected_options: Vec<String>,
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let enhance_config = {
+        let config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.enhance_config.clone()
+    };
+
+    Ok(crate::mcp::handlers::render_enhance_prompt(
+        &enhance_config,
+        &EnhanceContext {
+            original_text: &original_text,
+            selected_options: &selected_options,
+            project_path: project_path.as_deref(),
+        },
+    ))
+}
+
+/// 调用配置的模型接口直接改写草稿，返回结果供用户编辑后发送
+#[tauri::command]
+pub async fn llm_enhance_text(
+    original_text: String,
+    selected_options: Vec<String>,
+    project_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let enhance_config = {
+        let config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        config.enhance_config.clone()
+    };
+
+    enhance_with_llm(
+        &enhance_config,
+        &EnhanceContext {
+            original_text: &original_text,
+            selected_options: &selected_options,
+            project_path: project_path.as_deref(),
+        },
+    )
+    .await
+    .map_err(|e| format!("增强失败: {}", e))
+}
+
 #[tauri::command]
 pub async fn get_window_settings(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
     let config = state
@@ -483,6 +840,12 @@ pub async fn open_external_url(url: String) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn exit_app(app: AppHandle) -> Result<(), String> {
+    // 常驻模式下回答完成后只隐藏窗口，等待下一个请求
+    if crate::daemon::is_daemon_mode() {
+        crate::daemon::server::hide_main_window(&app);
+        return Ok(());
+    }
+
     // 直接调用强制退出，用于程序内部的退出操作（如MCP响应后退出）
     crate::ui::exit::force_exit_app(app).await
 }
@@ -871,6 +1234,110 @@ pub async fn update_shortcut_binding(
 
 
 
+/// 导出快捷模板和快捷键包
+///
+/// ID列表为空时导出全部；提供 `file_path` 时同时写入文件，格式默认按文件扩展名判断
+#[tauri::command]
+pub async fn export_prompt_pack(
+    prompt_ids: Vec<String>,
+    shortcut_ids: Vec<String>,
+    name: Option<String>,
+    format: Option<String>,
+    file_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let format = format
+        .or_else(|| {
+            file_path
+                .as_deref()
+                .and_then(|path| std::path::Path::new(path).extension())
+                .map(|ext| ext.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "json".to_string());
+    let format = PackFormat::parse(&format).map_err(|e| e.to_string())?;
+
+    let content = {
+        let config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+        export_pack(
+            &config.custom_prompt_config,
+            &config.shortcut_config,
+            &prompt_ids,
+            &shortcut_ids,
+            name,
+            format,
+        )
+        .map_err(|e| format!("导出模板包失败: {}", e))?
+    };
+
+    if let Some(path) = file_path {
+        std::fs::write(&path, &content).map_err(|e| format!("写入模板包失败: {}", e))?;
+    }
+
+    Ok(content)
+}
+
+/// 从文件或URL导入快捷模板和快捷键包
+///
+/// `strategy` 为 merge（跳过冲突项）或 overwrite（覆盖冲突项）；`dry_run` 时只返回冲突报告，不保存
+#[tauri::command]
+pub async fn import_prompt_pack(
+    source: String,
+    strategy: String,
+    dry_run: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ImportReport, String> {
+    let strategy = ImportStrategy::parse(&strategy).map_err(|e| e.to_string())?;
+
+    let content = if source.starts_with("http://") || source.starts_with("https://") {
+        let response = reqwest::get(&source)
+            .await
+            .map_err(|e| format!("下载模板包失败: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("下载模板包失败: HTTP {}", response.status()));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| format!("读取模板包失败: {}", e))?
+    } else {
+        std::fs::read_to_string(&source).map_err(|e| format!("读取模板包失败: {}", e))?
+    };
+
+    let pack = parse_pack(&content).map_err(|e| e.to_string())?;
+
+    let report = {
+        let mut config = state
+            .config
+            .lock()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+
+        if dry_run {
+            let mut prompt_config = config.custom_prompt_config.clone();
+            let mut shortcut_config = config.shortcut_config.clone();
+            return Ok(apply_pack(&mut prompt_config, &mut shortcut_config, pack, strategy));
+        }
+
+        let config = &mut *config;
+        apply_pack(
+            &mut config.custom_prompt_config,
+            &mut config.shortcut_config,
+            pack,
+            strategy,
+        )
+    };
+
+    // 保存配置到文件
+    save_config(&state, &app)
+        .await
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    Ok(report)
+}
+
 /// 重置快捷键为默认值
 #[tauri::command]
 pub async fn reset_shortcuts_to_default(
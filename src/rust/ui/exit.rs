@@ -98,6 +98,13 @@ pub async fn handle_system_exit_request(
     app: &AppHandle,
     is_manual_close: bool,
 ) -> Result<bool, String> {
+    // 常驻模式：关闭窗口或退出快捷键只取消当前请求并隐藏到托盘
+    if crate::daemon::is_daemon_mode() {
+        crate::daemon::server::cancel_pending_request(app);
+        crate::daemon::server::hide_main_window(app);
+        return Ok(true);
+    }
+
     // 如果是手动点击关闭按钮，直接退出
     if is_manual_close {
         perform_exit(app.clone()).await?;
@@ -0,0 +1,212 @@
+use crate::config::MemoryCategoryDef;
+use crate::mcp::tools::memory::{
+    known_projects, ChangeRecord, ChangeSource, KnownProject, LayeredMemory, MemoryCategory, MemoryEntry, MemoryFilter,
+    MemoryScope, SearchHit, DEFAULT_HISTORY_LIMIT, DEFAULT_SEARCH_LIMIT,
+};
+
+// 记忆浏览和编辑命令，与 ji 工具共用 MemoryManager，校验规则保持一致
+
+fn open_memory(project_path: &str) -> Result<LayeredMemory, String> {
+    LayeredMemory::open(project_path)
+        .map(|memory| memory.with_source(ChangeSource::Gui))
+        .map_err(|e| format!("打开项目记忆失败: {}", e))
+}
+
+/// 解析分类参数，空字符串视为未指定
+fn resolve_category(memory: &LayeredMemory, category: Option<&str>) -> Result<Option<MemoryCategory>, String> {
+    match category.map(str::trim).filter(|c| !c.is_empty()) {
+        None => Ok(None),
+        Some(key) => memory.categories().resolve(key).map(Some).ok_or_else(|| {
+            format!("未知的记忆分类: {}，可选值: {}", key, memory.categories().keys().join(", "))
+        }),
+    }
+}
+
+fn build_filter(
+    memory: &LayeredMemory,
+    scope: Option<MemoryScope>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<MemoryFilter, String> {
+    Ok(MemoryFilter {
+        scope,
+        category: resolve_category(memory, category.as_deref())?,
+        tags: tags.unwrap_or_default(),
+        all_branches: false,
+        include_expired: false,
+        keep_overridden: false,
+    })
+}
+
+/// 列出使用过记忆功能的项目
+#[tauri::command]
+pub async fn list_memory_projects() -> Result<Vec<KnownProject>, String> {
+    known_projects().map_err(|e| format!("读取项目列表失败: {}", e))
+}
+
+/// 项目可用的记忆分类，按召回优先级排列
+#[tauri::command]
+pub async fn get_memory_categories(project_path: String) -> Result<Vec<MemoryCategoryDef>, String> {
+    Ok(open_memory(&project_path)?.categories().defs().to_vec())
+}
+
+/// 列出记忆，未指定作用域时按 项目 > 团队 > 全局 合并
+#[tauri::command]
+pub async fn list_memories(
+    project_path: String,
+    scope: Option<MemoryScope>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<Vec<MemoryEntry>, String> {
+    let memory = open_memory(&project_path)?;
+    // 设置界面需要看到全部条目，包括其他分支的分支记忆、尚未整理掉的过期记忆和被高优先级作用域覆盖的记忆
+    let filter = MemoryFilter {
+        all_branches: true,
+        include_expired: true,
+        keep_overridden: true,
+        ..build_filter(&memory, scope, category, tags)?
+    };
+    memory.get_memories(&filter).map_err(|e| format!("读取记忆失败: {}", e))
+}
+
+/// 按相关度检索记忆
+#[tauri::command]
+pub async fn search_memories(
+    project_path: String,
+    query: String,
+    scope: Option<MemoryScope>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    if query.trim().is_empty() {
+        return Err("缺少检索关键词".to_string());
+    }
+
+    let memory = open_memory(&project_path)?;
+    let filter = build_filter(&memory, scope, category, tags)?;
+    memory
+        .search_memories(query.trim(), &filter, limit.filter(|&n| n > 0).unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .map_err(|e| format!("检索记忆失败: {}", e))
+}
+
+/// 编辑记忆内容、分类或标签，未提供的字段保持不变
+#[tauri::command]
+pub async fn update_memory_entry(
+    project_path: String,
+    id: String,
+    content: Option<String>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    scope: Option<MemoryScope>,
+) -> Result<MemoryEntry, String> {
+    let content = content.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    if content.is_none() && category.is_none() && tags.is_none() {
+        return Err("需要提供新的记忆内容、分类或标签".to_string());
+    }
+
+    let memory = open_memory(&project_path)?;
+    let category = resolve_category(&memory, category.as_deref())?;
+    memory
+        .locate(&id, scope)
+        .and_then(|layer| layer.update_memory(&id, content, category, tags))
+        .map_err(|e| format!("更新记忆失败: {}", e))
+}
+
+/// 将记忆移动到其他分类
+#[tauri::command]
+pub async fn move_memory_entry(
+    project_path: String,
+    id: String,
+    category: String,
+    scope: Option<MemoryScope>,
+) -> Result<MemoryEntry, String> {
+    let memory = open_memory(&project_path)?;
+    let category = resolve_category(&memory, Some(&category))?.ok_or_else(|| "缺少目标分类".to_string())?;
+    memory
+        .locate(&id, scope)
+        .and_then(|layer| layer.move_memory(&id, category))
+        .map_err(|e| format!("移动记忆失败: {}", e))
+}
+
+/// 将分支记忆提升为对所有分支生效
+#[tauri::command]
+pub async fn promote_memory_entry(
+    project_path: String,
+    id: String,
+    scope: Option<MemoryScope>,
+) -> Result<MemoryEntry, String> {
+    let memory = open_memory(&project_path)?;
+    memory
+        .locate(&id, scope)
+        .and_then(|layer| layer.promote_memory(&id))
+        .map_err(|e| format!("提升记忆失败: {}", e))
+}
+
+/// 删除记忆，返回被删除的条目
+#[tauri::command]
+pub async fn delete_memory_entry(
+    project_path: String,
+    id: String,
+    scope: Option<MemoryScope>,
+) -> Result<MemoryEntry, String> {
+    let memory = open_memory(&project_path)?;
+    memory
+        .locate(&id, scope)
+        .and_then(|layer| layer.delete_memory(&id))
+        .map_err(|e| format!("删除记忆失败: {}", e))
+}
+
+/// 变更历史，最近的在前，可按记忆ID筛选
+#[tauri::command]
+pub async fn list_memory_history(
+    project_path: String,
+    scope: Option<MemoryScope>,
+    memory_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ChangeRecord>, String> {
+    open_memory(&project_path)?
+        .history(scope, memory_id.as_deref(), limit.filter(|&n| n > 0).unwrap_or(DEFAULT_HISTORY_LIMIT))
+        .map_err(|e| format!("读取变更历史失败: {}", e))
+}
+
+/// 撤销一次变更，返回新追加的撤销记录
+#[tauri::command]
+pub async fn undo_memory_change(
+    project_path: String,
+    change_id: String,
+    scope: Option<MemoryScope>,
+) -> Result<ChangeRecord, String> {
+    let memory = open_memory(&project_path)?;
+    memory
+        .locate_change(&change_id, scope)
+        .and_then(|layer| layer.undo_change(&change_id))
+        .map_err(|e| format!("撤销变更失败: {}", e))
+}
+
+/// 在系统文件管理器中打开记忆目录，未指定作用域时打开项目记忆目录
+#[tauri::command]
+pub async fn open_memory_folder(project_path: String, scope: Option<MemoryScope>) -> Result<String, String> {
+    use std::process::Command;
+
+    let memory = open_memory(&project_path)?;
+    let dir = memory
+        .layer(scope.unwrap_or_default())
+        .map_err(|e| e.to_string())?
+        .memory_dir()
+        .to_path_buf();
+
+    let result = if cfg!(target_os = "windows") {
+        Command::new("explorer").arg(&dir).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(&dir).spawn()
+    } else {
+        // Linux 和其他 Unix 系统
+        Command::new("xdg-open").arg(&dir).spawn()
+    };
+
+    match result {
+        Ok(_) => Ok(dir.display().to_string()),
+        Err(e) => Err(format!("无法打开记忆目录: {}", e)),
+    }
+}
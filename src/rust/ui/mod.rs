@@ -3,6 +3,7 @@ pub mod window;
 pub mod audio;
 pub mod audio_assets;
 pub mod font_commands;
+pub mod memory_commands;
 pub mod updater;
 pub mod exit;
 pub mod window_events;
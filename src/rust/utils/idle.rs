@@ -0,0 +1,64 @@
+//! 桌面空闲时间检测
+//!
+//! 用于在用户离开电脑时把交互请求转发到Telegram。
+//! 各平台检测失败时返回 None，调用方应视为“未知”而不是“空闲”
+
+/// 获取距离上次键盘/鼠标输入的秒数
+#[cfg(target_os = "macos")]
+pub fn system_idle_seconds() -> Option<u64> {
+    // ioreg 输出中的 HIDIdleTime 单位为纳秒
+    let output = std::process::Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    stdout
+        .lines()
+        .find(|line| line.contains("\"HIDIdleTime\""))
+        .and_then(|line| line.rsplit('=').next())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(|nanos| nanos / 1_000_000_000)
+}
+
+/// 获取距离上次键盘/鼠标输入的秒数
+#[cfg(target_os = "linux")]
+pub fn system_idle_seconds() -> Option<u64> {
+    // 依赖 xprintidle（X11），输出单位为毫秒；Wayland 或未安装时返回 None
+    let output = std::process::Command::new("xprintidle").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|millis| millis / 1000)
+}
+
+/// 获取距离上次键盘/鼠标输入的秒数
+#[cfg(windows)]
+pub fn system_idle_seconds() -> Option<u64> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+
+    // SAFETY: info 已按要求初始化 cbSize，指针在调用期间有效
+    if unsafe { GetLastInputInfo(&mut info) } == 0 {
+        return None;
+    }
+
+    let now = unsafe { GetTickCount() };
+    Some(u64::from(now.wrapping_sub(info.dwTime) / 1000))
+}
+
+/// 其他平台暂不支持空闲检测
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+pub fn system_idle_seconds() -> Option<u64> {
+    None
+}
@@ -1,3 +1,5 @@
+pub mod idle;
 pub mod logger;
 
+pub use idle::system_idle_seconds;
 pub use logger::{LogConfig, init_logger, auto_init_logger};